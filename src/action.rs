@@ -0,0 +1,298 @@
+//! Bindings and action state as components, one set per entity.
+
+use crate::{KeyState, ShortcutState, Shortcuts};
+#[cfg(feature = "window")]
+use crate::{WindowKeys, WindowScope};
+use bevy::input::InputSystems;
+use bevy::input::keyboard::KeyCode;
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+/// An entity's own bindings, so that each local player (or each tool
+/// entity) can map the same actions to different keys.
+///
+/// Spawning it adds an [`ActionState<A>`], which [`ActionStatePlugin<A>`]
+/// updates from these bindings every frame.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_keyboard_shortcuts::{ActionState, ActionStatePlugin, ShortcutBindings, Shortcuts};
+///
+/// #[derive(Clone, PartialEq, Eq, Hash)]
+/// enum Action {
+///     Left,
+///     Right,
+///     Jump,
+/// }
+///
+/// #[derive(Component)]
+/// struct Player(u8);
+///
+/// fn spawn_players(mut commands: Commands) {
+///     commands.spawn((
+///         Player(1),
+///         ShortcutBindings::from_iter([
+///             (Action::Left, Shortcuts::repeating(&[KeyCode::KeyA])),
+///             (Action::Right, Shortcuts::repeating(&[KeyCode::KeyD])),
+///             (Action::Jump, Shortcuts::single_press(&[KeyCode::KeyW])),
+///         ]),
+///     ));
+///     commands.spawn((
+///         Player(2),
+///         ShortcutBindings::from_iter([
+///             (Action::Left, Shortcuts::repeating(&[KeyCode::ArrowLeft])),
+///             (Action::Right, Shortcuts::repeating(&[KeyCode::ArrowRight])),
+///             (Action::Jump, Shortcuts::single_press(&[KeyCode::ArrowUp])),
+///         ]),
+///     ));
+/// }
+///
+/// fn movement(players: Query<(&ActionState<Action>, &Player)>) {
+///     for (actions, player) in &players {
+///         if actions.pressed(&Action::Jump) {
+///             // Player `player.0` jumps
+///         }
+///     }
+/// }
+///
+/// App::new()
+///     .add_plugins(ActionStatePlugin::<Action>::default())
+///     .add_systems(Startup, spawn_players)
+///     .add_systems(Update, movement);
+/// ```
+#[derive(Component, Debug, Clone)]
+#[require(ActionState<A>)]
+pub struct ShortcutBindings<A: Eq + Hash + Send + Sync + 'static> {
+    bindings: HashMap<A, Shortcuts>,
+}
+
+impl<A: Eq + Hash + Send + Sync + 'static> Default for ShortcutBindings<A> {
+    fn default() -> Self {
+        Self {
+            bindings: HashMap::new(),
+        }
+    }
+}
+
+impl<A: Eq + Hash + Send + Sync + 'static> ShortcutBindings<A> {
+    /// Binds `action`, replacing any earlier binding.
+    pub fn insert(&mut self, action: A, binding: Shortcuts) -> &mut Self {
+        self.bindings.insert(action, binding);
+        self
+    }
+
+    /// Unbinds `action`, returning its binding.
+    pub fn remove(&mut self, action: &A) -> Option<Shortcuts> {
+        self.bindings.remove(action)
+    }
+
+    /// The binding of `action`.
+    pub fn get(&self, action: &A) -> Option<&Shortcuts> {
+        self.bindings.get(action)
+    }
+
+    /// Every action and its binding, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&A, &Shortcuts)> {
+        self.bindings.iter()
+    }
+}
+
+impl<A: Eq + Hash + Send + Sync + 'static> FromIterator<(A, Shortcuts)> for ShortcutBindings<A> {
+    fn from_iter<I: IntoIterator<Item = (A, Shortcuts)>>(iter: I) -> Self {
+        Self {
+            bindings: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Which of an entity's actions fired this frame, from its
+/// [`ShortcutBindings<A>`].
+///
+/// Each action keeps a [`ShortcutState`], so hold, tap and multi-tap
+/// triggers are timed per entity.
+#[derive(Component, Debug, Clone)]
+pub struct ActionState<A: Send + Sync + 'static> {
+    actions: HashMap<A, ShortcutState>,
+}
+
+impl<A: Send + Sync + 'static> Default for ActionState<A> {
+    fn default() -> Self {
+        Self {
+            actions: HashMap::new(),
+        }
+    }
+}
+
+impl<A: Clone + Eq + Hash + Send + Sync + 'static> ActionState<A> {
+    /// Advances every action by one frame. Actions no longer in `bindings`
+    /// are forgotten.
+    pub fn update<T: Default>(
+        &mut self,
+        bindings: &ShortcutBindings<A>,
+        keys: &impl KeyState,
+        time: &Time<T>,
+    ) {
+        self.actions
+            .retain(|action, _| bindings.bindings.contains_key(action));
+        for (action, binding) in &bindings.bindings {
+            self.actions
+                .entry(action.clone())
+                .or_default()
+                .update(binding, keys, time);
+        }
+    }
+
+    /// Whether `action` fired on the last update, as
+    /// [`Shortcuts::pressed`] would report it.
+    pub fn pressed(&self, action: &A) -> bool {
+        self.actions.get(action).is_some_and(|a| a.triggered())
+    }
+
+    /// How long the chord of `action` has been held, or `None` if it is not
+    /// held.
+    pub fn held_duration(&self, action: &A) -> Option<Duration> {
+        self.actions.get(action).and_then(|a| a.held_duration())
+    }
+
+    /// How long the chord of `action` had been held, on the frame it was
+    /// released.
+    pub fn released_after(&self, action: &A) -> Option<Duration> {
+        self.actions.get(action).and_then(|a| a.released_after())
+    }
+
+    /// The full state of `action`.
+    pub fn state(&self, action: &A) -> Option<&ShortcutState> {
+        self.actions.get(action)
+    }
+}
+
+/// Updates the [`ActionState<A>`] of every entity with
+/// [`ShortcutBindings<A>`] from the keyboard, after Bevy's input systems each
+/// frame.
+///
+/// With the `window` feature, an entity that also has a
+/// [`WindowScope`](crate::WindowScope) reads only that window's keys, from
+/// [`WindowKeys`](crate::WindowKeys); add
+/// [`WindowKeysPlugin`](crate::WindowKeysPlugin) for those.
+pub struct ActionStatePlugin<A>(PhantomData<A>);
+
+impl<A> Default for ActionStatePlugin<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: Clone + Eq + Hash + Send + Sync + 'static> Plugin for ActionStatePlugin<A> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreUpdate, update_action_states::<A>.after(InputSystems));
+        #[cfg(feature = "window")]
+        app.add_systems(
+            PreUpdate,
+            update_scoped_action_states::<A>
+                .after(crate::window::track_window_keys)
+                .run_if(resource_exists::<WindowKeys>),
+        );
+    }
+}
+
+/// Entities that read the keyboard of every window at once.
+#[cfg(feature = "window")]
+type Unscoped = Without<WindowScope>;
+#[cfg(not(feature = "window"))]
+type Unscoped = ();
+
+fn update_action_states<A: Clone + Eq + Hash + Send + Sync + 'static>(
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    mut entities: Query<(&ShortcutBindings<A>, &mut ActionState<A>), Unscoped>,
+) {
+    for (bindings, mut state) in &mut entities {
+        state.update(bindings, &keys, &time);
+    }
+}
+
+#[cfg(feature = "window")]
+fn update_scoped_action_states<A: Clone + Eq + Hash + Send + Sync + 'static>(
+    windows: Res<WindowKeys>,
+    time: Res<Time>,
+    mut entities: Query<(&ShortcutBindings<A>, &mut ActionState<A>, &WindowScope)>,
+) {
+    for (bindings, mut state, scope) in &mut entities {
+        state.update(bindings, windows.scoped(*scope), &time);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::time::TimePlugin;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum Action {
+        Left,
+        Jump,
+    }
+
+    fn player(left: KeyCode, jump: KeyCode) -> ShortcutBindings<Action> {
+        ShortcutBindings::from_iter([
+            (Action::Left, Shortcuts::repeating(&[left])),
+            (Action::Jump, Shortcuts::single_press(&[jump])),
+        ])
+    }
+
+    #[test]
+    fn each_entity_reads_its_own_bindings() {
+        let mut app = App::new();
+        app.add_plugins((TimePlugin, ActionStatePlugin::<Action>::default()))
+            .init_resource::<ButtonInput<KeyCode>>();
+        let one = app
+            .world_mut()
+            .spawn(player(KeyCode::KeyA, KeyCode::KeyW))
+            .id();
+        let two = app
+            .world_mut()
+            .spawn(player(KeyCode::ArrowLeft, KeyCode::ArrowUp))
+            .id();
+
+        let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keys.press(KeyCode::KeyA);
+        keys.press(KeyCode::ArrowUp);
+        app.update();
+        let state = |entity| app.world().get::<ActionState<Action>>(entity).unwrap();
+        assert!(state(one).pressed(&Action::Left));
+        assert!(!state(one).pressed(&Action::Jump));
+        assert!(state(two).pressed(&Action::Jump));
+        assert!(!state(two).pressed(&Action::Left));
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .clear();
+        app.update();
+        let state = |entity| app.world().get::<ActionState<Action>>(entity).unwrap();
+        assert!(state(one).pressed(&Action::Left), "repeating while held");
+        assert!(!state(two).pressed(&Action::Jump), "single press");
+        assert!(state(two).held_duration(&Action::Jump).is_some());
+    }
+
+    #[test]
+    fn unbound_actions_are_forgotten() {
+        let mut bindings = player(KeyCode::KeyA, KeyCode::KeyW);
+        let mut state = ActionState::default();
+        let mut keys = ButtonInput::<KeyCode>::default();
+        let time = Time::<()>::default();
+
+        keys.press(KeyCode::KeyW);
+        state.update(&bindings, &keys, &time);
+        assert!(state.pressed(&Action::Jump));
+
+        bindings.remove(&Action::Jump);
+        state.update(&bindings, &keys, &time);
+        assert!(state.state(&Action::Jump).is_none());
+        assert!(state.state(&Action::Left).is_some());
+    }
+}
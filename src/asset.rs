@@ -0,0 +1,836 @@
+//! Loading keymaps as assets, so edits to the file apply while the game runs.
+
+use crate::{
+    ActiveKeymap, Keymap, KeymapMigrations, KeymapProfile, KeymapProfiles, MigrationError,
+    VersionedKeymap,
+};
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoadError, AssetLoadFailedEvent, AssetLoader, LoadContext};
+use bevy::prelude::*;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::fmt;
+use std::sync::Arc;
+
+/// The file formats [`KeymapLoader`] reads, chosen by file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeymapFormat {
+    /// `.keymap.ron`
+    Ron,
+    /// `.keymap.json` — the YAML configuration shown in the crate docs,
+    /// written as JSON
+    Json,
+    /// `.keymap.toml`
+    Toml,
+}
+
+impl KeymapFormat {
+    /// The format for a file extension such as `keymap.ron` or `toml`.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.rsplit('.').next()? {
+            "ron" => Some(KeymapFormat::Ron),
+            "json" => Some(KeymapFormat::Json),
+            "toml" => Some(KeymapFormat::Toml),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for KeymapFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            KeymapFormat::Ron => "RON",
+            KeymapFormat::Json => "JSON",
+            KeymapFormat::Toml => "TOML",
+        })
+    }
+}
+
+/// Why a keymap file could not be loaded.
+#[derive(Debug)]
+pub enum KeymapLoadError {
+    /// The file could not be read
+    Io(std::io::Error),
+    /// The file extension is not one of [`KeymapFormat`]'s
+    UnknownFormat(String),
+    /// The file is not a valid keymap
+    Parse {
+        /// The format it was read as
+        format: KeymapFormat,
+        /// 1-based line of the problem
+        line: usize,
+        /// 1-based column of the problem
+        column: usize,
+        /// What is wrong there
+        message: String,
+    },
+    /// The file parsed, but could not be upgraded to the current version
+    Migration(MigrationError),
+}
+
+impl fmt::Display for KeymapLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KeymapLoadError::Io(error) => write!(f, "could not read keymap: {error}"),
+            KeymapLoadError::UnknownFormat(extension) => {
+                write!(f, "no keymap format for extension {extension:?}")
+            }
+            KeymapLoadError::Parse {
+                format,
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "invalid {format} keymap at line {line}, column {column}: {message}"
+            ),
+            KeymapLoadError::Migration(error) => write!(f, "could not upgrade keymap: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for KeymapLoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            KeymapLoadError::Io(error) => Some(error),
+            KeymapLoadError::Migration(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Reads a [`Keymap`] from `text` in `format`.
+///
+/// This is what [`KeymapLoader`] runs on each file when it has no
+/// migrations; it is public for loading keymaps outside the asset system,
+/// such as in tests or tools.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_keyboard_shortcuts::{KeymapFormat, KeymapLoadError, parse_keymap};
+///
+/// let keymap = parse_keymap(
+///     r#"
+/// [jump]
+/// shortcuts = [{ key = "Space" }]
+/// "#,
+///     KeymapFormat::Toml,
+/// )
+/// .unwrap();
+/// assert_eq!(keymap.get("jump").unwrap().to_string(), "Space");
+///
+/// let error = parse_keymap("{\n  \"jump\": {\"shortcuts\": [{\"key\": \"Spcae\"}]}\n}", KeymapFormat::Json);
+/// assert!(matches!(error, Err(KeymapLoadError::Parse { line: 2, .. })));
+/// ```
+pub fn parse_keymap(text: &str, format: KeymapFormat) -> Result<Keymap, KeymapLoadError> {
+    parse(text, format)
+}
+
+/// Reads a keymap file of any version from `text` in `format` and upgrades
+/// it with `migrations`, as [`KeymapMigrations::load`] does.
+///
+/// JSON and TOML files migrate in full. RON's enum variants do not survive
+/// the untyped read migrations work on, so a RON file — a plain action map,
+/// or `(version: 2, actions: { ... })` — must already deserialize in the
+/// current [`Shortcuts`](crate::Shortcuts) layout; its actions are then
+/// renamed, split and re-defaulted like any other.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_keyboard_shortcuts::{KeymapFormat, KeymapMigrations, Migration, parse_versioned_keymap};
+///
+/// let mut migrations = KeymapMigrations::new();
+/// migrations.to_version(2, [Migration::RenameAction { from: "fire".into(), to: "attack".into() }]);
+///
+/// let keymap = parse_versioned_keymap(
+///     "(version: 1, actions: { \"fire\": (shortcuts: [(key: KeyJ)]) })",
+///     KeymapFormat::Ron,
+///     &migrations,
+/// )
+/// .unwrap();
+/// assert_eq!(keymap.get("attack").unwrap().to_string(), "J");
+/// ```
+pub fn parse_versioned_keymap(
+    text: &str,
+    format: KeymapFormat,
+    migrations: &KeymapMigrations,
+) -> Result<Keymap, KeymapLoadError> {
+    let file = match format {
+        KeymapFormat::Ron => {
+            let file = match parse::<RonVersionedKeymap>(text, format) {
+                Ok(file) => VersionedKeymap {
+                    version: file.version,
+                    actions: action_map(&file.actions),
+                },
+                Err(versioned_error) => match parse::<Keymap>(text, format) {
+                    Ok(keymap) => VersionedKeymap {
+                        version: 1,
+                        actions: action_map(&keymap),
+                    },
+                    Err(error) => return Err(furthest(versioned_error, error)),
+                },
+            };
+            serde_json::to_value(file).expect("a versioned keymap serializes to JSON")
+        }
+        KeymapFormat::Json | KeymapFormat::Toml => parse::<Value>(text, format)?,
+    };
+    migrations.load(file).map_err(KeymapLoadError::Migration)
+}
+
+/// A versioned RON keymap file, read in the current layout.
+#[derive(Deserialize)]
+struct RonVersionedKeymap {
+    version: u32,
+    actions: Keymap,
+}
+
+fn action_map(keymap: &Keymap) -> serde_json::Map<String, Value> {
+    match serde_json::to_value(keymap) {
+        Ok(Value::Object(actions)) => actions,
+        _ => unreachable!("a keymap serializes to a JSON map"),
+    }
+}
+
+/// Of two attempts to read the same file, the error that got further into
+/// it, which is the one that says what the file was meant to be.
+fn furthest(a: KeymapLoadError, b: KeymapLoadError) -> KeymapLoadError {
+    let position = |error: &KeymapLoadError| match error {
+        KeymapLoadError::Parse { line, column, .. } => (*line, *column),
+        _ => (0, 0),
+    };
+    if position(&a) > position(&b) { a } else { b }
+}
+
+fn parse<T: DeserializeOwned>(text: &str, format: KeymapFormat) -> Result<T, KeymapLoadError> {
+    match format {
+        // Implicit `Some` so modifiers read as in the other formats:
+        // `control: RequirePressed` rather than `control: Some(RequirePressed)`.
+        KeymapFormat::Ron => ron::Options::default()
+            .with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+            .from_str(text)
+            .map_err(|error| KeymapLoadError::Parse {
+                format,
+                line: error.span.start.line,
+                column: error.span.start.col,
+                message: error.code.to_string(),
+            }),
+        KeymapFormat::Json => serde_json::from_str(text).map_err(|error| KeymapLoadError::Parse {
+            format,
+            line: error.line(),
+            column: error.column(),
+            message: error.to_string(),
+        }),
+        KeymapFormat::Toml => parse_toml(text),
+    }
+}
+
+/// TOML errors carry a byte span rather than a line and column.
+fn parse_toml<T: DeserializeOwned>(text: &str) -> Result<T, KeymapLoadError> {
+    toml_edit::de::from_str(text).map_err(|error| {
+        let offset = error.span().map_or(0, |span| span.start);
+        let (line, column) = line_column(text, offset);
+        KeymapLoadError::Parse {
+            format: KeymapFormat::Toml,
+            line,
+            column,
+            message: error.message().trim_end().to_string(),
+        }
+    })
+}
+
+/// 1-based line and column (in characters) of a byte offset.
+fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// Loads `*.keymap.ron`, `*.keymap.json` and `*.keymap.toml` files as
+/// [`Keymap`] assets.
+///
+/// With [`KeymapMigrations`], files of any version load, upgraded as by
+/// [`parse_versioned_keymap`]; without, each file must be a plain action map
+/// in the current layout.
+#[derive(Default, TypePath)]
+pub struct KeymapLoader {
+    migrations: Option<Arc<KeymapMigrations>>,
+}
+
+impl KeymapLoader {
+    /// A loader that upgrades every file with `migrations`.
+    pub fn with_migrations(migrations: impl Into<Arc<KeymapMigrations>>) -> Self {
+        Self {
+            migrations: Some(migrations.into()),
+        }
+    }
+}
+
+impl AssetLoader for KeymapLoader {
+    type Asset = Keymap;
+    type Settings = ();
+    type Error = KeymapLoadError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Keymap, KeymapLoadError> {
+        let extension = load_context
+            .path()
+            .get_full_extension()
+            .unwrap_or_default()
+            .to_string();
+        let format = KeymapFormat::from_extension(&extension)
+            .ok_or(KeymapLoadError::UnknownFormat(extension))?;
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(KeymapLoadError::Io)?;
+        let text = String::from_utf8(bytes).map_err(|error| {
+            KeymapLoadError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+        })?;
+        match &self.migrations {
+            Some(migrations) => parse_versioned_keymap(&text, format, migrations),
+            None => parse_keymap(&text, format),
+        }
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["keymap.ron", "keymap.json", "keymap.toml"]
+    }
+}
+
+/// The keymap file whose contents [`KeymapAssetPlugin`] keeps in
+/// [`ActiveKeymap`].
+///
+/// Each time the file loads — at startup, and again on every save when
+/// Bevy's `file_watcher` feature is on — its bindings replace
+/// `ActiveKeymap`. A file that fails to load changes nothing: the game keeps
+/// the last valid bindings and the failure is kept in
+/// [`last_error`](Self::last_error) until the next successful load.
+///
+/// With [`KeymapProfilesPlugin`](crate::KeymapProfilesPlugin) in the app,
+/// follow the file with [`for_profile`](Self::for_profile) instead: the file
+/// then becomes that profile's bindings, and the profile registry alone
+/// decides what lands in `ActiveKeymap`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use bevy::prelude::*;
+/// use bevy_keyboard_shortcuts::{KeymapAssetPlugin, KeymapSource};
+///
+/// fn load_keymap(mut commands: Commands, assets: Res<AssetServer>) {
+///     commands.insert_resource(KeymapSource::new(assets.load("controls.keymap.ron")));
+/// }
+///
+/// fn show_keymap_errors(source: Res<KeymapSource>) {
+///     if let Some(error) = source.last_error() {
+///         // e.g. "... invalid RON keymap at line 3, column 14: ..."
+///         eprintln!("{error}");
+///     }
+/// }
+///
+/// App::new()
+///     .add_plugins((DefaultPlugins, KeymapAssetPlugin::default()))
+///     .add_systems(Startup, load_keymap)
+///     .add_systems(Update, show_keymap_errors.run_if(resource_changed::<KeymapSource>));
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct KeymapSource {
+    handle: Handle<Keymap>,
+    profile: Option<String>,
+    last_error: Option<AssetLoadError>,
+}
+
+impl KeymapSource {
+    /// Follows the keymap asset behind `handle` into [`ActiveKeymap`].
+    pub fn new(handle: Handle<Keymap>) -> Self {
+        Self {
+            handle,
+            profile: None,
+            last_error: None,
+        }
+    }
+
+    /// Follows the keymap asset behind `handle` into the `keymap` of the
+    /// [`KeymapProfiles`] profile called `profile`, adding the profile if it
+    /// is missing.
+    ///
+    /// The profile's `based_on` and `overrides` are left alone, so profiles
+    /// built on top of it pick up every reload.
+    pub fn for_profile(handle: Handle<Keymap>, profile: impl Into<String>) -> Self {
+        Self {
+            profile: Some(profile.into()),
+            ..Self::new(handle)
+        }
+    }
+
+    /// The handle of the followed keymap.
+    pub fn handle(&self) -> &Handle<Keymap> {
+        &self.handle
+    }
+
+    /// The profile the keymap is loaded into, if it does not go straight to
+    /// [`ActiveKeymap`].
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    /// Why the file last failed to load, if it has failed since it last
+    /// loaded successfully.
+    ///
+    /// Parse failures carry a [`KeymapLoadError`] with the line and column;
+    /// reach it through [`AssetLoadError::AssetLoaderError`].
+    pub fn last_error(&self) -> Option<&AssetLoadError> {
+        self.last_error.as_ref()
+    }
+}
+
+/// Registers [`KeymapLoader`] and the [`Keymap`] asset, and copies the
+/// asset behind [`KeymapSource`] into [`ActiveKeymap`] whenever it loads.
+///
+/// Needs Bevy's `AssetPlugin`. A [`KeymapSource`] made with
+/// [`for_profile`](KeymapSource::for_profile) feeds the
+/// [`KeymapProfiles`] registry instead, leaving
+/// [`KeymapProfilesPlugin`](crate::KeymapProfilesPlugin) the only writer of
+/// `ActiveKeymap`; a source made with [`new`](KeymapSource::new) and the
+/// profiles plugin would both write it, so do not use the two together.
+///
+/// Games whose keymap layout has changed between releases load files
+/// through their [`KeymapMigrations`] with
+/// [`with_migrations`](Self::with_migrations).
+#[derive(Default)]
+pub struct KeymapAssetPlugin {
+    migrations: Option<Arc<KeymapMigrations>>,
+}
+
+impl KeymapAssetPlugin {
+    /// Loads keymap files of any version, upgraded with `migrations`.
+    pub fn with_migrations(migrations: impl Into<Arc<KeymapMigrations>>) -> Self {
+        Self {
+            migrations: Some(migrations.into()),
+        }
+    }
+}
+
+impl Plugin for KeymapAssetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Keymap>()
+            .register_asset_loader(KeymapLoader {
+                migrations: self.migrations.clone(),
+            })
+            .init_resource::<ActiveKeymap>()
+            .add_systems(
+                PreUpdate,
+                apply_keymap_source.run_if(resource_exists::<KeymapSource>),
+            );
+    }
+}
+
+fn apply_keymap_source(
+    mut loaded: MessageReader<AssetEvent<Keymap>>,
+    mut failed: MessageReader<AssetLoadFailedEvent<Keymap>>,
+    keymaps: Res<Assets<Keymap>>,
+    mut source: ResMut<KeymapSource>,
+    mut active: ResMut<ActiveKeymap>,
+    profiles: Option<ResMut<KeymapProfiles>>,
+) {
+    let id = source.handle.id();
+    for event in failed.read() {
+        if event.id == id {
+            source.last_error = Some(event.error.clone());
+        }
+    }
+    let reloaded = loaded.read().any(|event| match event {
+        AssetEvent::Added { id: changed } | AssetEvent::Modified { id: changed } => *changed == id,
+        _ => false,
+    });
+    if reloaded && let Some(keymap) = keymaps.get(id) {
+        match (&source.profile, profiles) {
+            (Some(name), Some(mut profiles)) => match profiles.get_mut(name) {
+                Some(profile) => profile.keymap = keymap.clone(),
+                None => {
+                    profiles.insert(name.clone(), KeymapProfile::new(keymap.clone()));
+                }
+            },
+            _ => active.0 = keymap.clone(),
+        }
+        source.last_error = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Shortcuts;
+
+    fn parse_error(text: &str, format: KeymapFormat) -> (usize, usize) {
+        match parse_keymap(text, format) {
+            Err(KeymapLoadError::Parse { line, column, .. }) => (line, column),
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn every_format_reads_the_same_keymap() {
+        let ron = r#"{
+            "jump": (shortcuts: [(key: Space)]),
+            "save": (shortcuts: [(key: KeyS, modifiers: (control: RequirePressed))]),
+        }"#;
+        let json = r#"{
+            "jump": {"shortcuts": [{"key": "Space"}]},
+            "save": {"shortcuts": [{"key": "KeyS", "modifiers": {"control": "RequirePressed"}}]}
+        }"#;
+        let toml = r#"
+            [jump]
+            shortcuts = [{ key = "Space" }]
+
+            [[save.shortcuts]]
+            key = "KeyS"
+            modifiers = { control = "RequirePressed" }
+        "#;
+
+        let expected = Keymap::from_iter([
+            ("jump", Shortcuts::single_press(&[KeyCode::Space])),
+            (
+                "save",
+                Shortcuts::single_press(&[KeyCode::KeyS]).with_ctrl(),
+            ),
+        ]);
+        assert_eq!(parse_keymap(ron, KeymapFormat::Ron).unwrap(), expected);
+        assert_eq!(parse_keymap(json, KeymapFormat::Json).unwrap(), expected);
+        assert_eq!(parse_keymap(toml, KeymapFormat::Toml).unwrap(), expected);
+    }
+
+    #[test]
+    fn parse_errors_carry_line_and_column() {
+        assert_eq!(
+            parse_error(
+                "{\n  \"jump\": (shortcuts: [(key: Spcae)]),\n}",
+                KeymapFormat::Ron
+            ),
+            (2, 30)
+        );
+        assert_eq!(
+            parse_error(
+                "{\n  \"jump\": {\"shortcuts\": [{\"key\" \"Space\"}]}\n}",
+                KeymapFormat::Json
+            ),
+            (2, 33)
+        );
+        assert_eq!(
+            parse_error("[jump]\nshortcuts = [{ key = }]\n", KeymapFormat::Toml),
+            (2, 22)
+        );
+        // A well-formed TOML file with a bad binding points at the value.
+        assert_eq!(
+            parse_error(
+                "[jump]\nshortcuts = [{ key = \"Space\" }]\n\n[fire]\nshortcuts = 3\n",
+                KeymapFormat::Toml
+            ),
+            (5, 13)
+        );
+        assert_eq!(
+            parse_error(
+                "[jump]\nshortcuts = [{ key = \"Spcae\" }]\n",
+                KeymapFormat::Toml
+            ),
+            (2, 22)
+        );
+    }
+
+    #[test]
+    fn the_source_keymap_replaces_the_active_one_when_it_changes() {
+        let mut app = App::new();
+        app.add_plugins((
+            bevy::app::TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            KeymapAssetPlugin::default(),
+        ));
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<Keymap>>()
+            .add(Keymap::from_iter([(
+                "jump",
+                Shortcuts::single_press(&[KeyCode::Space]),
+            )]));
+        app.insert_resource(KeymapSource::new(handle.clone()));
+        // Asset events are flushed at the end of a frame and applied at the
+        // start of the next.
+        app.update();
+        app.update();
+        let jump = |app: &App| {
+            app.world()
+                .resource::<ActiveKeymap>()
+                .get("jump")
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(jump(&app), "Space");
+
+        // What a hot reload does to the asset.
+        app.world_mut()
+            .resource_mut::<Assets<Keymap>>()
+            .get_mut(&handle)
+            .unwrap()
+            .insert("jump", Shortcuts::single_press(&[KeyCode::KeyW]));
+        app.update();
+        app.update();
+        assert_eq!(jump(&app), "W");
+    }
+
+    #[test]
+    fn a_profile_source_feeds_the_registry_instead_of_the_active_keymap() {
+        let mut app = App::new();
+        app.add_plugins((
+            bevy::app::TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            KeymapAssetPlugin::default(),
+            crate::KeymapProfilesPlugin,
+        ));
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<Keymap>>()
+            .add(Keymap::from_iter([(
+                "jump",
+                Shortcuts::single_press(&[KeyCode::Space]),
+            )]));
+        let mut left_handed = crate::KeymapOverrides::new();
+        left_handed.insert(
+            "jump",
+            crate::ActionOverride::Replace(Shortcuts::single_press(&[KeyCode::KeyJ])),
+        );
+        {
+            let mut profiles = app.world_mut().resource_mut::<KeymapProfiles>();
+            profiles.insert("Default", KeymapProfile::default());
+            profiles.insert(
+                "Left-handed",
+                KeymapProfile::based_on("Default", left_handed),
+            );
+        }
+        app.insert_resource(KeymapSource::for_profile(handle.clone(), "Default"));
+        app.update();
+        app.update();
+        app.world_mut()
+            .resource_mut::<KeymapProfiles>()
+            .select("Default")
+            .unwrap();
+        app.update();
+        let jump = |app: &App| {
+            app.world()
+                .resource::<ActiveKeymap>()
+                .get("jump")
+                .map(ToString::to_string)
+        };
+        assert_eq!(jump(&app).as_deref(), Some("Space"));
+
+        app.world_mut()
+            .resource_mut::<KeymapProfiles>()
+            .select("Left-handed")
+            .unwrap();
+        app.update();
+        assert_eq!(jump(&app).as_deref(), Some("J"));
+
+        // A reload changes the profile, and the registry keeps the
+        // left-handed override on top.
+        app.world_mut()
+            .resource_mut::<Assets<Keymap>>()
+            .get_mut(&handle)
+            .unwrap()
+            .insert("fire", Shortcuts::single_press(&[KeyCode::KeyF]));
+        // One frame more than without profiles: the registry resolves at the
+        // start of the frame after it changed.
+        app.update();
+        app.update();
+        app.update();
+        assert_eq!(jump(&app).as_deref(), Some("J"));
+        assert!(app.world().resource::<ActiveKeymap>().get("fire").is_some());
+    }
+
+    fn rename_fire() -> KeymapMigrations {
+        let mut migrations = KeymapMigrations::new();
+        migrations.to_version(
+            2,
+            [crate::Migration::RenameAction {
+                from: "fire".into(),
+                to: "attack".into(),
+            }],
+        );
+        migrations
+    }
+
+    #[test]
+    fn old_files_in_every_format_are_upgraded() {
+        let migrations = rename_fire();
+        for (text, format) in [
+            (
+                r#"{ "version": 1, "actions": { "fire": { "shortcuts": [{ "key": "KeyJ" }] } } }"#,
+                KeymapFormat::Json,
+            ),
+            (
+                r#"{ "fire": { "shortcuts": [{ "key": "KeyJ" }] } }"#,
+                KeymapFormat::Json,
+            ),
+            (
+                "version = 1\n[actions.fire]\nshortcuts = [{ key = \"KeyJ\" }]\n",
+                KeymapFormat::Toml,
+            ),
+            (
+                r#"(version: 1, actions: { "fire": (shortcuts: [(key: KeyJ)]) })"#,
+                KeymapFormat::Ron,
+            ),
+            (
+                r#"{ "fire": (shortcuts: [(key: KeyJ)]) }"#,
+                KeymapFormat::Ron,
+            ),
+        ] {
+            let keymap = parse_versioned_keymap(text, format, &migrations)
+                .unwrap_or_else(|e| panic!("{format}: {e}"));
+            assert_eq!(keymap.get("attack").unwrap().to_string(), "J", "{format}");
+            assert!(keymap.get("fire").is_none(), "{format}");
+        }
+    }
+
+    #[test]
+    fn versioned_ron_errors_point_into_the_file() {
+        let error = parse_versioned_keymap(
+            "(\n  version: 1,\n  actions: { \"fire\": (shortcuts: [(key: Spcae)]) },\n)",
+            KeymapFormat::Ron,
+            &rename_fire(),
+        );
+        assert!(
+            matches!(error, Err(KeymapLoadError::Parse { line: 3, .. })),
+            "{error:?}"
+        );
+        let error = parse_versioned_keymap(
+            r#"{ "version": 3, "actions": {} }"#,
+            KeymapFormat::Json,
+            &rename_fire(),
+        );
+        assert!(matches!(
+            error,
+            Err(KeymapLoadError::Migration(MigrationError::TooNew { .. }))
+        ));
+    }
+
+    /// A directory under the system temp dir, removed when dropped so a
+    /// failing test does not leave it behind.
+    struct TempDir(std::path::PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("{name}-{}", std::process::id()));
+            std::fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    /// Runs frames until `done`, for loads on the asset server's tasks.
+    fn update_until(app: &mut App, done: impl Fn(&App) -> bool) {
+        for _ in 0..1000 {
+            app.update();
+            if done(app) {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        panic!("the keymap never finished loading");
+    }
+
+    #[test]
+    fn a_broken_reload_keeps_the_active_keymap_and_reports_where() {
+        let dir = TempDir::new("keymap-reload");
+        let file = dir.0.join("controls.keymap.ron");
+        std::fs::write(&file, r#"{ "jump": (shortcuts: [(key: Space)]) }"#).unwrap();
+
+        let mut app = App::new();
+        app.add_plugins((
+            bevy::app::TaskPoolPlugin::default(),
+            AssetPlugin {
+                file_path: dir.0.to_string_lossy().into_owned(),
+                ..default()
+            },
+            KeymapAssetPlugin::default(),
+        ));
+        let handle = app
+            .world()
+            .resource::<AssetServer>()
+            .load("controls.keymap.ron");
+        app.insert_resource(KeymapSource::new(handle));
+        update_until(&mut app, |app| {
+            app.world().resource::<ActiveKeymap>().get("jump").is_some()
+        });
+        let loaded = app.world().resource::<ActiveKeymap>().0.clone();
+
+        std::fs::write(&file, "{\n  \"jump\": (shortcuts: [(key: Spcae)]),\n}").unwrap();
+        app.world()
+            .resource::<AssetServer>()
+            .reload("controls.keymap.ron");
+        update_until(&mut app, |app| {
+            app.world()
+                .resource::<KeymapSource>()
+                .last_error()
+                .is_some()
+        });
+
+        assert_eq!(app.world().resource::<ActiveKeymap>().0, loaded);
+        let Some(AssetLoadError::AssetLoaderError(error)) =
+            app.world().resource::<KeymapSource>().last_error()
+        else {
+            panic!("expected a loader error");
+        };
+        match error.error().downcast_ref::<KeymapLoadError>() {
+            Some(KeymapLoadError::Parse { line, column, .. }) => {
+                assert_eq!((*line, *column), (2, 30));
+            }
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn the_plugin_upgrades_old_files_with_its_migrations() {
+        let dir = TempDir::new("keymap-migrate");
+        std::fs::write(
+            dir.0.join("controls.keymap.json"),
+            r#"{ "version": 1, "actions": { "fire": { "shortcuts": [{ "key": "KeyJ" }] } } }"#,
+        )
+        .unwrap();
+
+        let mut app = App::new();
+        app.add_plugins((
+            bevy::app::TaskPoolPlugin::default(),
+            AssetPlugin {
+                file_path: dir.0.to_string_lossy().into_owned(),
+                ..default()
+            },
+            KeymapAssetPlugin::with_migrations(rename_fire()),
+        ));
+        let handle = app
+            .world()
+            .resource::<AssetServer>()
+            .load("controls.keymap.json");
+        app.insert_resource(KeymapSource::new(handle));
+        update_until(&mut app, |app| {
+            app.world()
+                .resource::<ActiveKeymap>()
+                .get("attack")
+                .is_some()
+        });
+        assert!(app.world().resource::<ActiveKeymap>().get("fire").is_none());
+    }
+}
@@ -0,0 +1,490 @@
+//! Virtual axes: pairs of bindings read as a `-1..=1` value or a `Vec2`.
+
+use crate::input::ButtonSource;
+use crate::{KeyState, Shortcuts};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// What an axis reads while both of its directions are held.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum OpposingInputs {
+    /// The directions cancel out to zero (DEFAULT)
+    #[default]
+    CancelOut,
+    /// The direction pressed most recently wins, so tapping `D` while `A` is
+    /// still held turns around at once. Needs an [`AxisState`] or
+    /// [`DPadState`] to remember the order.
+    LastPressedWins,
+}
+
+/// How fast a smoothed axis follows its bindings, in full deflections per
+/// second.
+///
+/// An acceleration of `4.0` takes a quarter of a second to reach full speed
+/// from rest; a deceleration of `8.0` stops from full speed in an eighth.
+/// A negative rate counts as its magnitude, and `NaN` as no smoothing.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct AxisSmoothing {
+    /// Rate while a direction is held, turning around included
+    pub acceleration: f32,
+    /// Rate back to rest once nothing is held
+    pub deceleration: f32,
+}
+
+/// A `-1..=1` value from a pair of bindings, such as `A`/`D` for walking.
+///
+/// Each direction reads as held while any of its alternatives is held,
+/// whatever its `repeats` flag or trigger. [`VirtualAxis::value`] reads it
+/// straight off the keyboard; ordering and smoothing need an [`AxisState`].
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_keyboard_shortcuts::{Shortcuts, VirtualAxis};
+///
+/// let walk = VirtualAxis::new(
+///     Shortcuts::repeating(&[KeyCode::KeyA, KeyCode::ArrowLeft]),
+///     Shortcuts::repeating(&[KeyCode::KeyD, KeyCode::ArrowRight]),
+/// );
+///
+/// let mut keys = ButtonInput::<KeyCode>::default();
+/// keys.press(KeyCode::ArrowLeft);
+/// assert_eq!(walk.value(&keys), -1.0);
+/// keys.press(KeyCode::KeyD);
+/// assert_eq!(walk.value(&keys), 0.0, "both held cancel out");
+/// ```
+#[derive(Reflect, Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct VirtualAxis {
+    /// Pushes the axis towards `-1`
+    pub negative: Shortcuts,
+    /// Pushes the axis towards `1`
+    pub positive: Shortcuts,
+    /// What both held at once reads as
+    #[serde(default)]
+    pub opposing: OpposingInputs,
+    /// How fast the value follows the bindings; `None` jumps at once
+    #[serde(default)]
+    pub smoothing: Option<AxisSmoothing>,
+}
+
+impl VirtualAxis {
+    /// Creates an axis that cancels out when both directions are held and is
+    /// not smoothed.
+    pub fn new(negative: Shortcuts, positive: Shortcuts) -> Self {
+        Self {
+            negative,
+            positive,
+            opposing: OpposingInputs::CancelOut,
+            smoothing: None,
+        }
+    }
+
+    /// Lets the direction pressed last win while both are held (chainable).
+    pub fn last_pressed_wins(mut self) -> Self {
+        self.opposing = OpposingInputs::LastPressedWins;
+        self
+    }
+
+    /// Eases the value towards the bindings at the given rates, in full
+    /// deflections per second (chainable).
+    pub fn smoothed(mut self, acceleration: f32, deceleration: f32) -> Self {
+        self.smoothing = Some(AxisSmoothing {
+            acceleration,
+            deceleration,
+        });
+        self
+    }
+
+    /// The axis as the keyboard holds it now: `-1`, `0` or `1`.
+    ///
+    /// Both directions held read `0` whatever [`VirtualAxis::opposing`] says,
+    /// and smoothing is not applied; use an [`AxisState`] for those.
+    pub fn value(&self, keys: &impl KeyState) -> f32 {
+        f32::from(held(&self.positive, keys)) - f32::from(held(&self.negative, keys))
+    }
+}
+
+/// A `Vec2` from four bindings, such as WASD, with `y` up.
+///
+/// Diagonals are normalised by default, so moving diagonally is no faster
+/// than moving straight; turn that off for grid movement with
+/// [`VirtualDPad::unnormalized`].
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_keyboard_shortcuts::{Shortcuts, VirtualDPad};
+///
+/// let movement = VirtualDPad::new(
+///     Shortcuts::repeating(&[KeyCode::KeyW]),
+///     Shortcuts::repeating(&[KeyCode::KeyS]),
+///     Shortcuts::repeating(&[KeyCode::KeyA]),
+///     Shortcuts::repeating(&[KeyCode::KeyD]),
+/// );
+///
+/// let mut keys = ButtonInput::<KeyCode>::default();
+/// keys.press(KeyCode::KeyW);
+/// keys.press(KeyCode::KeyD);
+/// assert!((movement.value(&keys) - Vec2::new(1.0, 1.0).normalize()).length() < 1e-6);
+/// ```
+#[derive(Reflect, Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct VirtualDPad {
+    /// Pushes `y` towards `1`
+    pub up: Shortcuts,
+    /// Pushes `y` towards `-1`
+    pub down: Shortcuts,
+    /// Pushes `x` towards `-1`
+    pub left: Shortcuts,
+    /// Pushes `x` towards `1`
+    pub right: Shortcuts,
+    /// What opposite directions held at once read as, on each axis
+    #[serde(default)]
+    pub opposing: OpposingInputs,
+    /// Whether diagonals are scaled back to length 1
+    #[serde(default = "normalize_by_default")]
+    pub normalize_diagonals: bool,
+    /// How fast the vector follows the bindings; `None` jumps at once
+    #[serde(default)]
+    pub smoothing: Option<AxisSmoothing>,
+}
+
+fn normalize_by_default() -> bool {
+    true
+}
+
+impl VirtualDPad {
+    /// Creates a pad that cancels out opposite directions, normalises
+    /// diagonals and is not smoothed.
+    pub fn new(up: Shortcuts, down: Shortcuts, left: Shortcuts, right: Shortcuts) -> Self {
+        Self {
+            up,
+            down,
+            left,
+            right,
+            opposing: OpposingInputs::CancelOut,
+            normalize_diagonals: true,
+            smoothing: None,
+        }
+    }
+
+    /// Lets the direction pressed last win on each axis while both are held
+    /// (chainable).
+    pub fn last_pressed_wins(mut self) -> Self {
+        self.opposing = OpposingInputs::LastPressedWins;
+        self
+    }
+
+    /// Leaves diagonals at `(±1, ±1)` (chainable).
+    pub fn unnormalized(mut self) -> Self {
+        self.normalize_diagonals = false;
+        self
+    }
+
+    /// Eases the vector towards the bindings at the given rates, in full
+    /// deflections per second (chainable).
+    pub fn smoothed(mut self, acceleration: f32, deceleration: f32) -> Self {
+        self.smoothing = Some(AxisSmoothing {
+            acceleration,
+            deceleration,
+        });
+        self
+    }
+
+    /// The vector as the keyboard holds it now, normalised if
+    /// [`VirtualDPad::normalize_diagonals`] is set.
+    ///
+    /// Opposite directions held cancel out whatever
+    /// [`VirtualDPad::opposing`] says, and smoothing is not applied; use a
+    /// [`DPadState`] for those.
+    pub fn value(&self, keys: &impl KeyState) -> Vec2 {
+        let x = f32::from(held(&self.right, keys)) - f32::from(held(&self.left, keys));
+        let y = f32::from(held(&self.up, keys)) - f32::from(held(&self.down, keys));
+        self.normalized(Vec2::new(x, y))
+    }
+
+    fn normalized(&self, value: Vec2) -> Vec2 {
+        if self.normalize_diagonals {
+            value.clamp_length_max(1.0)
+        } else {
+            value
+        }
+    }
+}
+
+/// The per-axis memory a [`VirtualAxis`] needs for
+/// [`OpposingInputs::LastPressedWins`] and smoothing.
+///
+/// Keep one per axis, next to it, and update it once a frame.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_keyboard_shortcuts::{AxisState, Shortcuts, VirtualAxis};
+///
+/// #[derive(Resource)]
+/// struct Steering {
+///     axis: VirtualAxis,
+///     state: AxisState,
+/// }
+///
+/// fn steer(keyboard: Res<ButtonInput<KeyCode>>, time: Res<Time>, mut steering: ResMut<Steering>) {
+///     let Steering { axis, state } = &mut *steering;
+///     let turn = state.update(axis, &keyboard, &time);
+///     // Turn the wheels by `turn`
+/// }
+///
+/// let axis = VirtualAxis::new(
+///     Shortcuts::repeating(&[KeyCode::KeyA]),
+///     Shortcuts::repeating(&[KeyCode::KeyD]),
+/// )
+/// .last_pressed_wins()
+/// .smoothed(4.0, 8.0);
+/// ```
+#[derive(Reflect, Debug, Clone, Default)]
+pub struct AxisState {
+    direction: DirectionMemory,
+    value: f32,
+}
+
+impl AxisState {
+    /// Advances the axis by one frame and returns its value.
+    pub fn update<T: Default>(
+        &mut self,
+        axis: &VirtualAxis,
+        keys: &impl KeyState,
+        time: &Time<T>,
+    ) -> f32 {
+        let target = self.direction.resolve(
+            held(&axis.negative, keys),
+            held(&axis.positive, keys),
+            axis.opposing,
+        );
+        self.value = match axis.smoothing {
+            Some(smoothing) => {
+                let rate = smoothing.rate(target != 0.0) * time.delta_secs();
+                self.value + (target - self.value).clamp(-rate, rate)
+            }
+            None => target,
+        };
+        self.value
+    }
+
+    /// The value the last update returned.
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+}
+
+/// The per-pad memory a [`VirtualDPad`] needs for
+/// [`OpposingInputs::LastPressedWins`] and smoothing, updated like an
+/// [`AxisState`].
+#[derive(Reflect, Debug, Clone, Default)]
+pub struct DPadState {
+    x: DirectionMemory,
+    y: DirectionMemory,
+    value: Vec2,
+}
+
+impl DPadState {
+    /// Advances the pad by one frame and returns its vector.
+    pub fn update<T: Default>(
+        &mut self,
+        pad: &VirtualDPad,
+        keys: &impl KeyState,
+        time: &Time<T>,
+    ) -> Vec2 {
+        let x = self
+            .x
+            .resolve(held(&pad.left, keys), held(&pad.right, keys), pad.opposing);
+        let y = self
+            .y
+            .resolve(held(&pad.down, keys), held(&pad.up, keys), pad.opposing);
+        let target = pad.normalized(Vec2::new(x, y));
+        self.value = match pad.smoothing {
+            Some(smoothing) => {
+                let rate = smoothing.rate(target != Vec2::ZERO) * time.delta_secs();
+                self.value.move_towards(target, rate)
+            }
+            None => target,
+        };
+        self.value
+    }
+
+    /// The vector the last update returned.
+    pub fn value(&self) -> Vec2 {
+        self.value
+    }
+}
+
+impl AxisSmoothing {
+    /// The rate to move at, never negative: keymap files can hold any
+    /// number, and `clamp` panics on a negative or `NaN` bound.
+    fn rate(&self, held: bool) -> f32 {
+        let rate = if held {
+            self.acceleration
+        } else {
+            self.deceleration
+        };
+        if rate.is_nan() {
+            f32::INFINITY
+        } else {
+            rate.abs()
+        }
+    }
+}
+
+/// Which of an axis's two directions were held last frame, and which of them
+/// was pressed most recently.
+#[derive(Reflect, Debug, Clone, Default)]
+struct DirectionMemory {
+    negative_held: bool,
+    positive_held: bool,
+    latest: f32,
+}
+
+impl DirectionMemory {
+    fn resolve(&mut self, negative: bool, positive: bool, opposing: OpposingInputs) -> f32 {
+        match (
+            negative && !self.negative_held,
+            positive && !self.positive_held,
+        ) {
+            (true, true) => self.latest = 0.0,
+            (true, false) => self.latest = -1.0,
+            (false, true) => self.latest = 1.0,
+            (false, false) => {}
+        }
+        self.negative_held = negative;
+        self.positive_held = positive;
+
+        match (negative, positive) {
+            (false, false) => 0.0,
+            (true, false) => -1.0,
+            (false, true) => 1.0,
+            (true, true) => match opposing {
+                OpposingInputs::CancelOut => 0.0,
+                OpposingInputs::LastPressedWins => self.latest,
+            },
+        }
+    }
+}
+
+/// Whether any alternative of `shortcuts` is held, as a direction of an axis.
+fn held(shortcuts: &Shortcuts, keys: &impl ButtonSource) -> bool {
+    shortcuts.iter().any(|shortcut| shortcut.pressed_in(keys))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn walk() -> VirtualAxis {
+        VirtualAxis::new(
+            Shortcuts::repeating(&[KeyCode::KeyA]),
+            Shortcuts::repeating(&[KeyCode::KeyD]),
+        )
+    }
+
+    #[test]
+    fn last_pressed_direction_wins_until_released() {
+        let axis = walk().last_pressed_wins();
+        let mut state = AxisState::default();
+        let mut keys = ButtonInput::<KeyCode>::default();
+        let time = Time::<()>::default();
+
+        keys.press(KeyCode::KeyA);
+        assert_eq!(state.update(&axis, &keys, &time), -1.0);
+        keys.press(KeyCode::KeyD);
+        assert_eq!(state.update(&axis, &keys, &time), 1.0);
+        assert_eq!(walk().value(&keys), 0.0, "cancels out by default");
+        keys.release(KeyCode::KeyD);
+        assert_eq!(state.update(&axis, &keys, &time), -1.0, "back to A");
+
+        keys.release(KeyCode::KeyA);
+        assert_eq!(state.update(&axis, &keys, &time), 0.0);
+        keys.press(KeyCode::KeyA);
+        keys.press(KeyCode::KeyD);
+        state.update(&axis, &keys, &time);
+        assert_eq!(state.value(), 0.0, "pressed together: no winner");
+    }
+
+    #[test]
+    fn smoothing_accelerates_and_decelerates_at_its_rates() {
+        let axis = walk().smoothed(4.0, 10.0);
+        let mut state = AxisState::default();
+        let mut keys = ButtonInput::<KeyCode>::default();
+        let mut time = Time::<()>::default();
+        let mut frame = |state: &mut AxisState, keys: &ButtonInput<KeyCode>| {
+            time.advance_by(Duration::from_millis(100));
+            state.update(&axis, keys, &time)
+        };
+
+        keys.press(KeyCode::KeyD);
+        let rising: Vec<f32> = (0..3).map(|_| frame(&mut state, &keys)).collect();
+        for (value, expected) in rising.iter().zip([0.4, 0.8, 1.0]) {
+            assert!((value - expected).abs() < 1e-5, "{rising:?}");
+        }
+
+        keys.release(KeyCode::KeyD);
+        assert_eq!(frame(&mut state, &keys), 0.0, "10/s stops within 0.1s");
+    }
+
+    #[test]
+    fn out_of_range_rates_from_a_file_do_not_panic() {
+        let axis: VirtualAxis = serde_json::from_value(serde_json::json!({
+            "negative": { "shortcuts": [{ "key": "KeyA" }] },
+            "positive": { "shortcuts": [{ "key": "KeyD" }] },
+            "smoothing": { "acceleration": -4.0, "deceleration": -10.0 }
+        }))
+        .unwrap();
+        let mut state = AxisState::default();
+        let mut keys = ButtonInput::<KeyCode>::default();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_millis(100));
+
+        keys.press(KeyCode::KeyD);
+        assert!((state.update(&axis, &keys, &time) - 0.4).abs() < 1e-5);
+
+        let axis = VirtualAxis {
+            smoothing: Some(AxisSmoothing {
+                acceleration: f32::NAN,
+                deceleration: f32::NAN,
+            }),
+            ..axis
+        };
+        assert_eq!(state.update(&axis, &keys, &time), 1.0);
+    }
+
+    #[test]
+    fn dpad_normalises_diagonals_unless_told_not_to() {
+        let pad = VirtualDPad::new(
+            Shortcuts::repeating(&[KeyCode::KeyW]),
+            Shortcuts::repeating(&[KeyCode::KeyS]),
+            Shortcuts::repeating(&[KeyCode::KeyA]),
+            Shortcuts::repeating(&[KeyCode::KeyD]),
+        );
+        let mut keys = ButtonInput::<KeyCode>::default();
+        keys.press(KeyCode::KeyW);
+        keys.press(KeyCode::KeyA);
+
+        assert!((pad.value(&keys).length() - 1.0).abs() < 1e-6);
+        assert_eq!(
+            pad.clone().unnormalized().value(&keys),
+            Vec2::new(-1.0, 1.0)
+        );
+
+        keys.press(KeyCode::KeyS);
+        let mut state = DPadState::default();
+        let vector = state.update(&pad.last_pressed_wins(), &keys, &Time::<()>::default());
+        assert_eq!(vector, Vec2::new(-1.0, 0.0), "W, A and S pressed together");
+
+        let json = r#"{"up":{"shortcuts":[{"key":"KeyW"}]},"down":{"shortcuts":[]},
+            "left":{"shortcuts":[]},"right":{"shortcuts":[]}}"#;
+        let read: VirtualDPad = serde_json::from_str(json).unwrap();
+        assert!(read.normalize_diagonals);
+        assert_eq!(read.opposing, OpposingInputs::CancelOut);
+    }
+}
@@ -0,0 +1,25 @@
+//! Writes the JSON Schema of keymap files, to the path given or to stdout.
+//!
+//! ```sh
+//! cargo run --bin keymap_schema -- keymap.schema.json
+//! ```
+
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let schema = serde_json::to_string_pretty(&bevy_keyboard_shortcuts::keymap_schema())
+        .expect("a JSON value always serializes");
+    match std::env::args().nth(1) {
+        Some(path) => match std::fs::write(&path, schema + "\n") {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("could not write {path}: {error}");
+                ExitCode::FAILURE
+            }
+        },
+        None => {
+            println!("{schema}");
+            ExitCode::SUCCESS
+        }
+    }
+}
@@ -0,0 +1,351 @@
+//! Latching shortcut transitions so `FixedUpdate` gameplay sees each one once.
+
+use crate::{KeyState, Shortcuts};
+use bevy::input::InputSystems;
+use bevy::input::keyboard::KeyCode;
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+/// Shortcut transitions latched from every render frame for gameplay that runs
+/// in `FixedUpdate`.
+///
+/// [`Shortcuts::pressed`] on a non-repeating binding reads `just_pressed`,
+/// which is only true for the render frame the key went down. A fixed step
+/// runs zero, one or several times per render frame, so the press is either
+/// missed (no tick that frame) or seen by every tick (several). The buffer
+/// latches each press and release and reports it to exactly one fixed tick —
+/// the first one after it happened.
+///
+/// A binding can also carry a *grace window*: its press stays available to
+/// [`consume_press`](Self::consume_press) for that long after the fact, so a
+/// jump pressed a few frames before landing still happens on landing.
+///
+/// Add [`ActionBufferPlugin`] to latch in `PreUpdate` and end each tick in
+/// `FixedPostUpdate`; [`latch`](Self::latch) and [`end_tick`](Self::end_tick)
+/// are public for driving it by hand.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_keyboard_shortcuts::{ActionBuffer, ActionBufferPlugin, Shortcuts};
+/// use std::time::Duration;
+///
+/// #[derive(Clone, PartialEq, Eq, Hash)]
+/// enum Action {
+///     Jump,
+///     Fire,
+/// }
+///
+/// fn setup(mut buffer: ResMut<ActionBuffer<Action>>) {
+///     buffer
+///         .bind_with_grace(
+///             Action::Jump,
+///             Shortcuts::single_press(&[KeyCode::Space]),
+///             Duration::from_millis(100),
+///         )
+///         .bind(Action::Fire, Shortcuts::single_press(&[KeyCode::KeyJ]));
+/// }
+///
+/// fn gameplay(mut buffer: ResMut<ActionBuffer<Action>>) {
+///     let on_ground = true;
+///     if on_ground && buffer.consume_press(&Action::Jump) {
+///         // Jump, even if Space went down just before landing.
+///     }
+///     if buffer.just_pressed(&Action::Fire) {
+///         // Fire exactly once per press, however many ticks run per frame.
+///     }
+/// }
+///
+/// App::new()
+///     .add_plugins(ActionBufferPlugin::<Action>::default())
+///     .add_systems(Startup, setup)
+///     .add_systems(FixedUpdate, gameplay);
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct ActionBuffer<A> {
+    actions: HashMap<A, BufferedAction>,
+}
+
+/// One bound action's latched state.
+#[derive(Debug, Clone)]
+struct BufferedAction {
+    binding: Shortcuts,
+    grace: Duration,
+    /// Whether the chord was held at the last latch
+    held: bool,
+    /// A press not yet reported to a fixed tick
+    press_pending: bool,
+    /// A release not yet reported to a fixed tick
+    release_pending: bool,
+    /// When the last unconsumed press happened, while inside the grace window
+    press_at: Option<Duration>,
+}
+
+impl<A> Default for ActionBuffer<A> {
+    fn default() -> Self {
+        Self {
+            actions: HashMap::new(),
+        }
+    }
+}
+
+impl<A: Eq + Hash> ActionBuffer<A> {
+    /// Binds `action` with no grace window, replacing any earlier binding.
+    pub fn bind(&mut self, action: A, binding: Shortcuts) -> &mut Self {
+        self.bind_with_grace(action, binding, Duration::ZERO)
+    }
+
+    /// Binds `action`, keeping each press available to
+    /// [`consume_press`](Self::consume_press) for `grace` after it happened.
+    pub fn bind_with_grace(&mut self, action: A, binding: Shortcuts, grace: Duration) -> &mut Self {
+        self.actions.insert(
+            action,
+            BufferedAction {
+                binding,
+                grace,
+                held: false,
+                press_pending: false,
+                release_pending: false,
+                press_at: None,
+            },
+        );
+        self
+    }
+
+    /// Records this render frame's transitions. Run once per frame, after
+    /// Bevy has updated `ButtonInput`.
+    ///
+    /// Presses and releases are those of any alternative's chord, whatever
+    /// the binding's [`Trigger`](crate::Trigger).
+    pub fn latch<T: Default>(&mut self, keys: &impl KeyState, time: &Time<T>) {
+        let now = time.elapsed();
+        for action in self.actions.values_mut() {
+            let shortcuts = action.binding.iter();
+            action.held = false;
+            for shortcut in shortcuts {
+                action.held |= shortcut.pressed(keys);
+                if shortcut.just_pressed(keys) {
+                    action.press_pending = true;
+                    action.press_at = Some(now);
+                }
+                action.release_pending |= shortcut.just_released(keys);
+            }
+            if action
+                .press_at
+                .is_some_and(|at| now.saturating_sub(at) > action.grace)
+            {
+                action.press_at = None;
+            }
+        }
+    }
+
+    /// Marks the latched transitions as seen. Run at the end of every fixed
+    /// tick.
+    pub fn end_tick(&mut self) {
+        for action in self.actions.values_mut() {
+            action.press_pending = false;
+            action.release_pending = false;
+        }
+    }
+
+    /// Whether `action` was pressed since the previous fixed tick.
+    pub fn just_pressed(&self, action: &A) -> bool {
+        self.actions.get(action).is_some_and(|a| a.press_pending)
+    }
+
+    /// Whether `action` was released since the previous fixed tick.
+    pub fn just_released(&self, action: &A) -> bool {
+        self.actions.get(action).is_some_and(|a| a.release_pending)
+    }
+
+    /// Whether `action` was held at the last render frame.
+    pub fn pressed(&self, action: &A) -> bool {
+        self.actions.get(action).is_some_and(|a| a.held)
+    }
+
+    /// Takes the press of `action` if one happened within its grace window
+    /// and has not been consumed yet.
+    ///
+    /// Unlike [`just_pressed`](Self::just_pressed), this is not tied to the
+    /// first tick after the press: gameplay calls it whenever it *could* act
+    /// on the press, and the press is spent the first time it returns `true`.
+    pub fn consume_press(&mut self, action: &A) -> bool {
+        self.actions
+            .get_mut(action)
+            .and_then(|a| a.press_at.take())
+            .is_some()
+    }
+}
+
+/// Adds an [`ActionBuffer<A>`] resource and the systems that latch it each
+/// frame and end each fixed tick.
+pub struct ActionBufferPlugin<A>(PhantomData<A>);
+
+impl<A> Default for ActionBufferPlugin<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: Eq + Hash + Send + Sync + 'static> Plugin for ActionBufferPlugin<A> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActionBuffer<A>>()
+            .add_systems(PreUpdate, latch_action_buffer::<A>.after(InputSystems))
+            .add_systems(FixedPostUpdate, end_action_buffer_tick::<A>);
+    }
+}
+
+fn latch_action_buffer<A: Eq + Hash + Send + Sync + 'static>(
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    mut buffer: ResMut<ActionBuffer<A>>,
+) {
+    buffer.latch(&keys, &time);
+}
+
+fn end_action_buffer_tick<A: Eq + Hash + Send + Sync + 'static>(
+    mut buffer: ResMut<ActionBuffer<A>>,
+) {
+    buffer.end_tick();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::time::{TimePlugin, TimeUpdateStrategy};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum Action {
+        Jump,
+    }
+
+    fn jump_buffer(grace: Duration) -> ActionBuffer<Action> {
+        let mut buffer = ActionBuffer::default();
+        buffer.bind_with_grace(
+            Action::Jump,
+            Shortcuts::single_press(&[KeyCode::Space]),
+            grace,
+        );
+        buffer
+    }
+
+    /// Runs one render frame with `ticks` fixed ticks, returning how many of
+    /// them saw the jump as just pressed.
+    fn frame(
+        buffer: &mut ActionBuffer<Action>,
+        keys: &mut ButtonInput<KeyCode>,
+        time: &mut Time,
+        ticks: usize,
+    ) -> usize {
+        time.advance_by(Duration::from_millis(16));
+        buffer.latch(keys, time);
+        keys.clear();
+        (0..ticks)
+            .filter(|_| {
+                let seen = buffer.just_pressed(&Action::Jump);
+                buffer.end_tick();
+                seen
+            })
+            .count()
+    }
+
+    #[test]
+    fn a_press_is_seen_by_exactly_one_tick() {
+        let mut buffer = jump_buffer(Duration::ZERO);
+        let mut keys = ButtonInput::<KeyCode>::default();
+        let mut time = Time::<()>::default();
+
+        // Several ticks in the press frame: only the first sees it.
+        keys.press(KeyCode::Space);
+        assert_eq!(frame(&mut buffer, &mut keys, &mut time, 3), 1);
+
+        // No tick in the press frame: carried over to the next frame's tick.
+        keys.release(KeyCode::Space);
+        frame(&mut buffer, &mut keys, &mut time, 1);
+        keys.press(KeyCode::Space);
+        assert_eq!(frame(&mut buffer, &mut keys, &mut time, 0), 0);
+        assert_eq!(frame(&mut buffer, &mut keys, &mut time, 2), 1);
+    }
+
+    #[test]
+    fn releases_are_latched_too() {
+        let mut buffer = jump_buffer(Duration::ZERO);
+        let mut keys = ButtonInput::<KeyCode>::default();
+        let mut time = Time::<()>::default();
+
+        keys.press(KeyCode::Space);
+        frame(&mut buffer, &mut keys, &mut time, 1);
+        assert!(buffer.pressed(&Action::Jump));
+        keys.release(KeyCode::Space);
+        frame(&mut buffer, &mut keys, &mut time, 0);
+        assert!(buffer.just_released(&Action::Jump));
+        assert!(!buffer.pressed(&Action::Jump));
+        buffer.end_tick();
+        assert!(!buffer.just_released(&Action::Jump));
+    }
+
+    #[test]
+    fn a_buffered_press_survives_its_grace_window_then_expires() {
+        let mut buffer = jump_buffer(Duration::from_millis(100));
+        let mut keys = ButtonInput::<KeyCode>::default();
+        let mut time = Time::<()>::default();
+
+        keys.press(KeyCode::Space);
+        frame(&mut buffer, &mut keys, &mut time, 1);
+        for _ in 0..5 {
+            frame(&mut buffer, &mut keys, &mut time, 1); // 80ms in the air
+        }
+        assert!(buffer.consume_press(&Action::Jump), "landed within 100ms");
+        assert!(!buffer.consume_press(&Action::Jump), "spent");
+
+        keys.release(KeyCode::Space);
+        frame(&mut buffer, &mut keys, &mut time, 1);
+        keys.press(KeyCode::Space);
+        frame(&mut buffer, &mut keys, &mut time, 1);
+        for _ in 0..7 {
+            frame(&mut buffer, &mut keys, &mut time, 1); // 112ms in the air
+        }
+        assert!(!buffer.consume_press(&Action::Jump), "too late");
+    }
+
+    #[derive(Resource, Default)]
+    struct TicksSeen(usize);
+
+    #[test]
+    fn plugin_delivers_a_press_to_one_fixed_tick() {
+        let mut app = App::new();
+        app.add_plugins((TimePlugin, ActionBufferPlugin::<Action>::default()))
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<TicksSeen>()
+            .insert_resource(Time::<Fixed>::from_duration(Duration::from_millis(5)))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                16,
+            )))
+            .add_systems(
+                FixedUpdate,
+                |buffer: Res<ActionBuffer<Action>>, mut seen: ResMut<TicksSeen>| {
+                    seen.0 += usize::from(buffer.just_pressed(&Action::Jump));
+                },
+            );
+        app.world_mut()
+            .resource_mut::<ActionBuffer<Action>>()
+            .bind(Action::Jump, Shortcuts::single_press(&[KeyCode::Space]));
+        app.update();
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Space);
+        // 16ms frames at a 5ms timestep run three ticks per frame.
+        for _ in 0..4 {
+            app.update();
+            app.world_mut()
+                .resource_mut::<ButtonInput<KeyCode>>()
+                .clear();
+        }
+        assert_eq!(app.world().resource::<TicksSeen>().0, 1);
+    }
+}
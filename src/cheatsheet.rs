@@ -0,0 +1,384 @@
+//! Cheat sheets of the bindings, as Markdown, HTML or plain text.
+
+use crate::{Shortcut, Shortcuts};
+use std::fmt::Write;
+
+/// One row of a [`CheatSheet`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheatSheetEntry {
+    /// The heading it is listed under; entries with an empty category come
+    /// first, under no heading
+    pub category: String,
+    /// The action as players know it, such as "Quick save"
+    pub name: String,
+    /// What the action does; may be empty
+    pub description: String,
+    /// Its bindings
+    pub shortcuts: Shortcuts,
+}
+
+/// A printable list of actions and their bindings, grouped by category.
+///
+/// The same sheet renders as Markdown tables for a manual
+/// ([`to_markdown`](Self::to_markdown)), a standalone page with keycaps
+/// ([`to_html`](Self::to_html)), or aligned columns for a console or an
+/// in-game overlay ([`to_plain_text`](Self::to_plain_text)). Categories appear
+/// in the order their first entry was added, and entries in the order they
+/// were added. Bindings are written the way [`Shortcut`]'s `Display` writes
+/// them (`Ctrl + S`), alternatives separated by commas.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy::prelude::KeyCode;
+/// use bevy_keyboard_shortcuts::{CheatSheet, Shortcuts};
+///
+/// let sheet = CheatSheet::new("Controls")
+///     .entry("Movement", "Jump", "Jump over gaps", Shortcuts::single_press(&[KeyCode::Space]))
+///     .entry("Game", "Quick save", "", Shortcuts::single_press(&[KeyCode::KeyS]).with_ctrl())
+///     .entry("Game", "Screenshot", "", Shortcuts::single_press(&[]))
+///     .skip_unbound(true);
+///
+/// assert_eq!(
+///     sheet.to_plain_text(),
+///     "Controls\n\
+///      ========\n\
+///      \n\
+///      Movement\n  \
+///      Jump        Space     Jump over gaps\n\
+///      \n\
+///      Game\n  \
+///      Quick save  Ctrl + S\n"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheatSheet {
+    title: String,
+    entries: Vec<CheatSheetEntry>,
+    skip_unbound: bool,
+}
+
+impl CheatSheet {
+    /// Creates an empty sheet headed `title`.
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            entries: Vec::new(),
+            skip_unbound: false,
+        }
+    }
+
+    /// Adds an action (chainable).
+    pub fn entry(
+        mut self,
+        category: impl Into<String>,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        shortcuts: Shortcuts,
+    ) -> Self {
+        self.push(CheatSheetEntry {
+            category: category.into(),
+            name: name.into(),
+            description: description.into(),
+            shortcuts,
+        });
+        self
+    }
+
+    /// Adds an action.
+    pub fn push(&mut self, entry: CheatSheetEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Leaves out actions with no bindings, and categories left empty by
+    /// that (chainable). By default they are listed with an empty keys column.
+    pub fn skip_unbound(mut self, skip: bool) -> Self {
+        self.skip_unbound = skip;
+        self
+    }
+
+    /// The sheet as a `#` title and a `##` section with a table per category.
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# {}\n", markdown_escape(&self.title));
+        for (category, entries) in self.groups() {
+            if !category.is_empty() {
+                let _ = write!(out, "\n## {}\n", markdown_escape(category));
+            }
+            out += "\n| Action | Keys | Description |\n| --- | --- | --- |\n";
+            for entry in entries {
+                let _ = writeln!(
+                    out,
+                    "| {} | {} | {} |",
+                    markdown_escape(&entry.name),
+                    markdown_escape(&keys_text(&entry.shortcuts)),
+                    markdown_escape(&entry.description),
+                );
+            }
+        }
+        out
+    }
+
+    /// The sheet as a standalone HTML page, each key drawn as a `<kbd>`
+    /// keycap.
+    pub fn to_html(&self) -> String {
+        let title = html_escape(&self.title);
+        let mut out = format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{title}</title>\n<style>\n{HTML_STYLE}</style>\n</head>\n<body>\n\
+             <h1>{title}</h1>\n"
+        );
+        for (category, entries) in self.groups() {
+            if !category.is_empty() {
+                let _ = writeln!(out, "<h2>{}</h2>", html_escape(category));
+            }
+            out += "<table>\n<thead><tr><th>Action</th><th>Keys</th><th>Description</th></tr></thead>\n<tbody>\n";
+            for entry in entries {
+                let keys = entry
+                    .shortcuts
+                    .iter()
+                    .map(keycaps)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let _ = writeln!(
+                    out,
+                    "<tr><td>{}</td><td>{keys}</td><td>{}</td></tr>",
+                    html_escape(&entry.name),
+                    html_escape(&entry.description),
+                );
+            }
+            out += "</tbody>\n</table>\n";
+        }
+        out += "</body>\n</html>\n";
+        out
+    }
+
+    /// The sheet as underlined title, category headings and indented rows
+    /// whose columns line up across the whole sheet.
+    pub fn to_plain_text(&self) -> String {
+        let groups = self.groups();
+        let rows = || groups.iter().flat_map(|(_, entries)| entries);
+        let width = |column: fn(&CheatSheetEntry) -> String| {
+            rows()
+                .map(|entry| column(entry).chars().count())
+                .max()
+                .unwrap_or(0)
+        };
+        let name_width = width(|entry| entry.name.clone());
+        let keys_width = width(|entry| keys_text(&entry.shortcuts));
+
+        let mut out = format!(
+            "{}\n{}\n",
+            self.title,
+            "=".repeat(self.title.chars().count())
+        );
+        for (category, entries) in &groups {
+            out += "\n";
+            if !category.is_empty() {
+                let _ = writeln!(out, "{category}");
+            }
+            for entry in entries {
+                let line = format!(
+                    "  {:name_width$}  {:keys_width$}  {}",
+                    entry.name,
+                    keys_text(&entry.shortcuts),
+                    entry.description,
+                );
+                let _ = writeln!(out, "{}", line.trim_end());
+            }
+        }
+        out
+    }
+
+    /// The listed entries by category, in first-seen order.
+    fn groups(&self) -> Vec<(&str, Vec<&CheatSheetEntry>)> {
+        let mut groups: Vec<(&str, Vec<&CheatSheetEntry>)> = Vec::new();
+        let listed = self
+            .entries
+            .iter()
+            .filter(|entry| !self.skip_unbound || entry.shortcuts.iter().next().is_some());
+        for entry in listed {
+            match groups.iter_mut().find(|(c, _)| *c == entry.category) {
+                Some((_, entries)) => entries.push(entry),
+                None => groups.push((&entry.category, vec![entry])),
+            }
+        }
+        // Uncategorised entries lead, since they have no heading of their own.
+        groups.sort_by_key(|(category, _)| !category.is_empty());
+        groups
+    }
+}
+
+/// Keycap styling for [`CheatSheet::to_html`].
+const HTML_STYLE: &str = "\
+body { font-family: system-ui, sans-serif; margin: 2em; }
+table { border-collapse: collapse; margin-bottom: 1.5em; }
+th, td { text-align: left; padding: 0.3em 1em 0.3em 0; }
+kbd {
+  display: inline-block;
+  min-width: 1.2em;
+  padding: 0.1em 0.4em;
+  border: 1px solid #aaa;
+  border-bottom-width: 3px;
+  border-radius: 4px;
+  background: #f7f7f7;
+  font: 0.9em ui-monospace, monospace;
+  text-align: center;
+}
+";
+
+/// The bindings as `Display` writes each alternative.
+fn keys_text(shortcuts: &Shortcuts) -> String {
+    shortcuts
+        .iter()
+        .map(Shortcut::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// `Ctrl + S` as `<kbd>` keycaps.
+fn keycaps(shortcut: &Shortcut) -> String {
+    shortcut
+        .modifiers
+        .display_names()
+        .into_iter()
+        .chain([shortcut.key_str()])
+        .map(|part| format!("<kbd>{}</kbd>", html_escape(&part)))
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+fn markdown_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '|' | '<' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::input::keyboard::KeyCode;
+
+    fn sheet() -> CheatSheet {
+        CheatSheet::new("Controls")
+            .entry(
+                "Movement",
+                "Move left",
+                "",
+                Shortcuts::repeating(&[KeyCode::KeyA, KeyCode::ArrowLeft]),
+            )
+            .entry(
+                "Editing",
+                "Redo",
+                "Redo the last undone change",
+                Shortcuts::single_press(&[KeyCode::KeyZ])
+                    .with_ctrl()
+                    .with_shift(),
+            )
+            .entry(
+                "Movement",
+                "Jump",
+                "",
+                Shortcuts::single_press(&[KeyCode::Space]),
+            )
+            .entry("Editing", "Toggle grid", "", Shortcuts::single_press(&[]))
+            .entry(
+                "",
+                "Command | palette",
+                "Search *every* action",
+                Shortcuts::single_press(&[KeyCode::Backquote]),
+            )
+    }
+
+    #[test]
+    fn markdown_groups_entries_into_tables() {
+        assert_eq!(
+            sheet().to_markdown(),
+            "# Controls\n\
+             \n\
+             | Action | Keys | Description |\n\
+             | --- | --- | --- |\n\
+             | Command \\| palette | \\` | Search \\*every\\* action |\n\
+             \n\
+             ## Movement\n\
+             \n\
+             | Action | Keys | Description |\n\
+             | --- | --- | --- |\n\
+             | Move left | A, Left |  |\n\
+             | Jump | Space |  |\n\
+             \n\
+             ## Editing\n\
+             \n\
+             | Action | Keys | Description |\n\
+             | --- | --- | --- |\n\
+             | Redo | Ctrl + Shift + Z | Redo the last undone change |\n\
+             | Toggle grid |  |  |\n"
+        );
+    }
+
+    #[test]
+    fn plain_text_aligns_columns_across_categories() {
+        let text = sheet().skip_unbound(true).to_plain_text();
+
+        assert_eq!(
+            text,
+            "Controls\n\
+             ========\n\
+             \n  \
+             Command | palette  `                 Search *every* action\n\
+             \n\
+             Movement\n  \
+             Move left          A, Left\n  \
+             Jump               Space\n\
+             \n\
+             Editing\n  \
+             Redo               Ctrl + Shift + Z  Redo the last undone change\n"
+        );
+    }
+
+    #[test]
+    fn html_draws_keycaps_and_escapes_text() {
+        let html = CheatSheet::new("Tips & tricks")
+            .entry(
+                "<Editing>",
+                "Redo",
+                "",
+                Shortcuts::single_press(&[KeyCode::KeyZ, KeyCode::KeyY]).with_ctrl(),
+            )
+            .to_html();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Tips &amp; tricks</title>"));
+        assert!(html.contains("<h2>&lt;Editing&gt;</h2>"));
+        assert!(html.contains("<td><kbd>Ctrl</kbd> + <kbd>Z</kbd>, <kbd>Y</kbd></td>"));
+        assert!(html.ends_with("</html>\n"));
+    }
+
+    #[test]
+    fn skipping_unbound_actions_drops_empty_categories() {
+        let sheet = CheatSheet::new("Controls")
+            .entry("Debug", "Wireframe", "", Shortcuts::single_press(&[]))
+            .entry(
+                "Game",
+                "Pause",
+                "",
+                Shortcuts::single_press(&[KeyCode::Escape]),
+            );
+
+        assert!(sheet.to_markdown().contains("## Debug"));
+        let skipped = sheet.skip_unbound(true).to_markdown();
+        assert!(!skipped.contains("Debug"));
+        assert!(skipped.contains("| Pause | Esc |  |"));
+    }
+}
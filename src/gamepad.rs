@@ -0,0 +1,338 @@
+//! Gamepad buttons as bindable inputs, with held gamepad buttons as modifiers.
+
+use crate::input::InputButton;
+use crate::{Shortcut, Shortcuts, Trigger};
+use bevy::input::gamepad::{Gamepad, GamepadButton};
+use std::borrow::Cow;
+
+/// Whose names a UI shows for gamepad buttons.
+///
+/// Bevy names buttons by position (`South` is the bottom face button), so a
+/// binding reads `A` on an Xbox controller, `Cross` on a PlayStation one and
+/// `B` on a Nintendo one. [`Shortcut`]'s `Display` uses [`GamepadGlyphs::Xbox`];
+/// [`Shortcuts::to_string_with`] takes the style to use.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy::input::gamepad::GamepadButton;
+/// use bevy_keyboard_shortcuts::{GamepadGlyphs, Shortcuts};
+///
+/// let dodge = Shortcuts::gamepad(&[GamepadButton::East]).holding(GamepadButton::LeftTrigger);
+/// assert_eq!(dodge.to_string(), "LB + B");
+/// assert_eq!(dodge.to_string_with(GamepadGlyphs::PlayStation), "L1 + Circle");
+/// assert_eq!(dodge.to_string_with(GamepadGlyphs::Nintendo), "L + A");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum GamepadGlyphs {
+    /// `A`, `B`, `X`, `Y`, `LB`, `RT`, `View`, `Menu`
+    #[default]
+    Xbox,
+    /// `Cross`, `Circle`, `Square`, `Triangle`, `L1`, `R2`, `Create`, `Options`
+    PlayStation,
+    /// `B`, `A`, `Y`, `X`, `L`, `ZR`, `Minus`, `Plus`
+    Nintendo,
+}
+
+impl GamepadGlyphs {
+    /// The style matching a controller's USB vendor (Microsoft, Sony or
+    /// Nintendo), if it reports one of those.
+    pub fn for_gamepad(gamepad: &Gamepad) -> Option<Self> {
+        match gamepad.vendor_id()? {
+            0x045e => Some(GamepadGlyphs::Xbox),
+            0x054c => Some(GamepadGlyphs::PlayStation),
+            0x057e => Some(GamepadGlyphs::Nintendo),
+            _ => None,
+        }
+    }
+
+    /// The name of `button` in this style.
+    pub fn name(self, button: GamepadButton) -> Cow<'static, str> {
+        use GamepadButton::*;
+        use GamepadGlyphs::*;
+
+        let name = match (self, button) {
+            (Xbox, South) | (Nintendo, East) => "A",
+            (Xbox, East) | (Nintendo, South) => "B",
+            (Xbox, West) | (Nintendo, North) => "X",
+            (Xbox, North) | (Nintendo, West) => "Y",
+            (PlayStation, South) => "Cross",
+            (PlayStation, East) => "Circle",
+            (PlayStation, West) => "Square",
+            (PlayStation, North) => "Triangle",
+            (Xbox, LeftTrigger) => "LB",
+            (Xbox, LeftTrigger2) => "LT",
+            (Xbox, RightTrigger) => "RB",
+            (Xbox, RightTrigger2) => "RT",
+            (PlayStation, LeftTrigger) => "L1",
+            (PlayStation, LeftTrigger2) => "L2",
+            (PlayStation, RightTrigger) => "R1",
+            (PlayStation, RightTrigger2) => "R2",
+            (Nintendo, LeftTrigger) => "L",
+            (Nintendo, LeftTrigger2) => "ZL",
+            (Nintendo, RightTrigger) => "R",
+            (Nintendo, RightTrigger2) => "ZR",
+            (Xbox, Select) => "View",
+            (Xbox, Start) => "Menu",
+            (Xbox, Mode) => "Xbox",
+            (PlayStation, Select) => "Create",
+            (PlayStation, Start) => "Options",
+            (PlayStation, Mode) => "PS",
+            (Nintendo, Select) => "Minus",
+            (Nintendo, Start) => "Plus",
+            (Nintendo, Mode) => "Home",
+            (Xbox, LeftThumb) => "LS",
+            (Xbox, RightThumb) => "RS",
+            (PlayStation, LeftThumb) => "L3",
+            (PlayStation, RightThumb) => "R3",
+            (Nintendo, LeftThumb) => "L Stick",
+            (Nintendo, RightThumb) => "R Stick",
+            (_, DPadUp) => "D-Pad Up",
+            (_, DPadDown) => "D-Pad Down",
+            (_, DPadLeft) => "D-Pad Left",
+            (_, DPadRight) => "D-Pad Right",
+            (_, C) => "C",
+            (_, Z) => "Z",
+            (_, Other(n)) => return Cow::Owned(format!("Button {n}")),
+        };
+        Cow::Borrowed(name)
+    }
+}
+
+impl Shortcut {
+    /// Checks if the shortcut is held on `gamepad`, its gamepad modifiers
+    /// included. Keyboard bindings never match here, and keyboard modifier
+    /// requirements count every modifier as released.
+    pub fn pressed_on_gamepad(&self, gamepad: &Gamepad) -> bool {
+        self.pressed_in(gamepad)
+    }
+
+    /// Checks if the shortcut's button was just pressed on `gamepad` while
+    /// its gamepad modifiers are held.
+    pub fn just_pressed_on_gamepad(&self, gamepad: &Gamepad) -> bool {
+        self.just_pressed_in(gamepad)
+    }
+
+    /// Checks if the shortcut's button was just released on `gamepad` while
+    /// its gamepad modifiers are held.
+    pub fn just_released_on_gamepad(&self, gamepad: &Gamepad) -> bool {
+        self.just_released_in(gamepad)
+    }
+
+    /// The shortcut as `Display` writes it, gamepad buttons named in `glyphs`
+    /// style.
+    pub fn to_string_with(&self, glyphs: GamepadGlyphs) -> String {
+        let mut parts: Vec<Cow<str>> = self
+            .modifiers
+            .required_names()
+            .into_iter()
+            .map(Cow::Borrowed)
+            .collect();
+        parts.extend(self.modifiers.gamepad.iter().map(|&held| glyphs.name(held)));
+        parts.push(match self.input {
+            InputButton::Gamepad(button) => glyphs.name(button),
+            input => Cow::Owned(input.to_string()),
+        });
+        parts.join(" + ")
+    }
+}
+
+impl Shortcuts {
+    /// Creates non-repeating shortcuts from gamepad buttons (no modifiers).
+    /// Check them with [`Shortcuts::pressed_on_gamepad`] or
+    /// [`Shortcuts::pressed_on_any_gamepad`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bevy::input::gamepad::GamepadButton;
+    /// use bevy::prelude::KeyCode;
+    /// use bevy_keyboard_shortcuts::Shortcuts;
+    ///
+    /// // Space on the keyboard, A on a controller
+    /// let jump = Shortcuts::single_press(&[KeyCode::Space])
+    ///     .or(Shortcuts::gamepad(&[GamepadButton::South]));
+    /// assert_eq!(jump.to_string(), "Space, A");
+    /// ```
+    pub fn gamepad(buttons: &[GamepadButton]) -> Self {
+        Self::from_inputs(buttons.iter().map(|&b| b.into()), false, Trigger::Press)
+    }
+
+    /// Creates shortcuts that fire every frame a gamepad button is held (no
+    /// modifiers).
+    pub fn gamepad_held(buttons: &[GamepadButton]) -> Self {
+        Self::from_inputs(buttons.iter().map(|&b| b.into()), true, Trigger::Press)
+    }
+
+    /// Requires `button` held on the same gamepad for the first shortcut, the
+    /// way [`Shortcuts::with_ctrl`] requires Ctrl. Chain it to hold several.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug mode if `button` is already required.
+    pub fn holding(mut self, button: GamepadButton) -> Self {
+        if let Some(shortcut) = self.shortcuts.first_mut() {
+            debug_assert!(
+                !shortcut.modifiers.gamepad.contains(&button),
+                "{button:?} already held"
+            );
+            shortcut.modifiers.gamepad.push(button);
+        }
+        self
+    }
+
+    /// Checks if any alternative fires on `gamepad`, as [`Shortcuts::pressed`]
+    /// does for the keyboard. Only gamepad alternatives can; check the
+    /// keyboard ones with [`Shortcuts::pressed`] as usual.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use bevy_keyboard_shortcuts::Shortcuts;
+    ///
+    /// #[derive(Component)]
+    /// struct Player(Entity);
+    ///
+    /// fn jump(players: Query<&Player>, gamepads: Query<&Gamepad>) {
+    ///     let jump = Shortcuts::gamepad(&[GamepadButton::South]);
+    ///     for player in &players {
+    ///         if gamepads.get(player.0).is_ok_and(|pad| jump.pressed_on_gamepad(pad)) {
+    ///             // This player jumps
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn pressed_on_gamepad(&self, gamepad: &Gamepad) -> bool {
+        self.pressed_in(gamepad)
+    }
+
+    /// Checks if any alternative fires on any of `gamepads`, for single-player
+    /// games that take input from whichever controller is in use. A gamepad
+    /// modifier must be held on the gamepad the button is pressed on.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use bevy_keyboard_shortcuts::Shortcuts;
+    ///
+    /// fn pause(keyboard: Res<ButtonInput<KeyCode>>, gamepads: Query<&Gamepad>) {
+    ///     let pause = Shortcuts::single_press(&[KeyCode::Escape])
+    ///         .or(Shortcuts::gamepad(&[GamepadButton::Start]));
+    ///     if pause.pressed(&keyboard) || pause.pressed_on_any_gamepad(&gamepads) {
+    ///         // Open the pause menu
+    ///     }
+    /// }
+    /// ```
+    pub fn pressed_on_any_gamepad<'a>(
+        &self,
+        gamepads: impl IntoIterator<Item = &'a Gamepad>,
+    ) -> bool {
+        gamepads
+            .into_iter()
+            .any(|gamepad| self.pressed_on_gamepad(gamepad))
+    }
+
+    /// The alternatives as `Display` writes them, gamepad buttons named in
+    /// `glyphs` style.
+    pub fn to_string_with(&self, glyphs: GamepadGlyphs) -> String {
+        self.shortcuts
+            .iter()
+            .map(|shortcut| shortcut.to_string_with(glyphs))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::input::ButtonInput;
+    use bevy::input::keyboard::KeyCode;
+
+    fn pad(held: &[GamepadButton]) -> Gamepad {
+        let mut gamepad = Gamepad::default();
+        for &button in held {
+            gamepad.digital_mut().press(button);
+        }
+        gamepad
+    }
+
+    #[test]
+    fn gamepad_modifiers_must_be_held_on_the_same_gamepad() {
+        let heavy = Shortcuts::gamepad(&[GamepadButton::West]).holding(GamepadButton::LeftTrigger);
+        let light = Shortcuts::gamepad(&[GamepadButton::West]);
+
+        assert!(!heavy.pressed_on_gamepad(&pad(&[GamepadButton::West])));
+        assert!(light.pressed_on_gamepad(&pad(&[GamepadButton::West])));
+        assert!(heavy.pressed_on_gamepad(&pad(&[GamepadButton::LeftTrigger, GamepadButton::West])));
+
+        let split = [
+            pad(&[GamepadButton::LeftTrigger]),
+            pad(&[GamepadButton::West]),
+        ];
+        assert!(!heavy.pressed_on_any_gamepad(&split), "LB on another pad");
+        assert!(light.pressed_on_any_gamepad(&split));
+
+        let mut keys = ButtonInput::<KeyCode>::default();
+        keys.press(KeyCode::KeyQ);
+        let either = Shortcuts::single_press(&[KeyCode::KeyQ]).or(heavy);
+        assert!(either.pressed(&keys));
+        keys.clear();
+        assert!(!either.pressed_on_gamepad(&pad(&[GamepadButton::West])));
+        assert!(
+            !Shortcuts::single_press(&[KeyCode::KeyQ]).pressed_on_any_gamepad(&split),
+            "keys are not on gamepads"
+        );
+    }
+
+    #[test]
+    fn glyphs_name_buttons_by_controller_family() {
+        let names = |glyphs: GamepadGlyphs| {
+            [
+                GamepadButton::South,
+                GamepadButton::East,
+                GamepadButton::RightTrigger2,
+                GamepadButton::Start,
+                GamepadButton::Other(20),
+            ]
+            .map(|button| glyphs.name(button).into_owned())
+        };
+
+        assert_eq!(
+            names(GamepadGlyphs::Xbox),
+            ["A", "B", "RT", "Menu", "Button 20"]
+        );
+        assert_eq!(
+            names(GamepadGlyphs::PlayStation),
+            ["Cross", "Circle", "R2", "Options", "Button 20"]
+        );
+        assert_eq!(
+            names(GamepadGlyphs::Nintendo),
+            ["B", "A", "ZR", "Plus", "Button 20"]
+        );
+    }
+
+    #[test]
+    fn gamepad_bindings_read_and_write_gamepad_fields() {
+        let json = r#"{"gamepad":"North","modifiers":{"gamepad":["RightTrigger"]}}"#;
+        let shortcut: Shortcut = serde_json::from_str(json).unwrap();
+
+        assert_eq!(shortcut.input, InputButton::Gamepad(GamepadButton::North));
+        assert_eq!(shortcut.modifiers.gamepad, [GamepadButton::RightTrigger]);
+        assert_eq!(shortcut.to_string(), "RB + Y");
+        let written = serde_json::to_value(&shortcut).unwrap();
+        assert_eq!(written["modifiers"]["gamepad"][0], "RightTrigger");
+        assert_eq!(
+            serde_json::from_value::<Shortcut>(written).unwrap(),
+            shortcut
+        );
+
+        let key = serde_json::to_value(Shortcuts::single_press(&[KeyCode::KeyA])).unwrap();
+        assert!(
+            key["shortcuts"][0]["modifiers"].get("gamepad").is_none(),
+            "keyboard bindings stay as they were written"
+        );
+    }
+}
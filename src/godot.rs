@@ -0,0 +1,753 @@
+//! Converting keymaps to and from the `[input]` section of Godot's
+//! `project.godot`.
+
+use crate::schema::key_code_names;
+use crate::{InputButton, Keymap, ModifierType, Modifiers, Shortcut, Shortcuts, Trigger};
+use bevy::input::keyboard::KeyCode;
+use std::fmt;
+
+/// The result of [`import_godot_input`]: the actions that could be read, and
+/// a report on the events that could not.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GodotImport {
+    /// Every action of the section, with the key events it could represent
+    pub keymap: Keymap,
+    /// Events that were skipped or read only in part
+    pub issues: Vec<GodotIssue>,
+}
+
+/// The result of [`export_godot_input`]: the `[input]` section, and a report
+/// on the bindings Godot cannot represent.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GodotExport {
+    /// The `[input]` section, ready to replace the one in `project.godot`
+    pub section: String,
+    /// Bindings that were left out or written only in part
+    pub issues: Vec<GodotIssue>,
+}
+
+/// One binding that could not be converted as written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GodotIssue {
+    /// The action it belongs to
+    pub action: String,
+    /// What could not be converted
+    pub problem: GodotProblem,
+}
+
+/// Why a binding could not be converted as written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GodotProblem {
+    /// A Godot event other than a key, such as `InputEventMouseButton`.
+    /// Skipped on import.
+    UnsupportedEvent(String),
+    /// A Godot key code with no [`KeyCode`]. Skipped on import.
+    UnknownGodotKey(u32),
+    /// A key event with only a layout-dependent `keycode`. Imported as the
+    /// physical key in that position on a US layout.
+    LogicalKeycode,
+    /// A key with no Godot key code. Skipped on export.
+    UnknownKey(KeyCode),
+    /// A binding to something other than a key, such as a mouse button.
+    /// Skipped on export.
+    UnsupportedInput(InputButton),
+    /// Godot events can only require modifiers, not forbid them. Exported
+    /// with the modifier ignored.
+    ModifierExclusion,
+    /// Godot actions have no triggers; that is up to the code reading them.
+    /// Exported as a plain press.
+    Trigger(Trigger),
+    /// Godot actions do not say whether holding them repeats; that is up to
+    /// the code reading them. Exported as a single press.
+    Repeating,
+}
+
+impl fmt::Display for GodotProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GodotProblem::UnsupportedEvent(class) => write!(f, "{class} events are not supported"),
+            GodotProblem::UnknownGodotKey(code) => write!(f, "unknown Godot key code {code}"),
+            GodotProblem::LogicalKeycode => {
+                f.write_str("read a layout-dependent keycode as a physical key")
+            }
+            GodotProblem::UnknownKey(key) => write!(f, "{key:?} has no Godot key code"),
+            GodotProblem::UnsupportedInput(input) => write!(f, "{input} cannot be exported"),
+            GodotProblem::ModifierExclusion => {
+                f.write_str("Godot cannot require a modifier to be released")
+            }
+            GodotProblem::Trigger(trigger) => write!(f, "Godot has no {trigger:?} trigger"),
+            GodotProblem::Repeating => f.write_str("Godot actions cannot repeat while held"),
+        }
+    }
+}
+
+/// Why a `project.godot` could not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GodotParseError {
+    /// 1-based line of the problem
+    pub line: usize,
+    /// 1-based column of the problem
+    pub column: usize,
+    /// What is wrong there
+    pub message: String,
+}
+
+impl fmt::Display for GodotParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid project.godot at line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for GodotParseError {}
+
+/// Reads the actions of a Godot 4 `[input]` section.
+///
+/// `text` is a whole `project.godot` — other sections are skipped — or just
+/// the section's entries. Each `InputEventKey` becomes one alternative of its
+/// action: its `physical_keycode` is the key, and the modifiers it has
+/// pressed are required while the rest are ignored, which is how Godot
+/// matches actions by default.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_keyboard_shortcuts::import_godot_input;
+///
+/// let project = r#"
+/// [input]
+///
+/// quick_save={
+/// "deadzone": 0.5,
+/// "events": [Object(InputEventKey,"resource_local_to_scene":false,"resource_name":"","device":-1,"window_id":0,"alt_pressed":false,"shift_pressed":false,"ctrl_pressed":true,"meta_pressed":false,"pressed":false,"keycode":0,"physical_keycode":83,"key_label":0,"unicode":115,"location":0,"echo":false,"script":null)
+/// ]
+/// }
+/// "#;
+/// let import = import_godot_input(project).unwrap();
+/// assert_eq!(import.keymap.get("quick_save").unwrap().to_string(), "Ctrl + S");
+/// ```
+pub fn import_godot_input(text: &str) -> Result<GodotImport, GodotParseError> {
+    let mut parser = Parser::new(text);
+    let has_sections = text.lines().any(|line| line.trim_start().starts_with('['));
+    if has_sections && !parser.seek_section("input") {
+        return Ok(GodotImport::default());
+    }
+
+    let mut import = GodotImport::default();
+    while let Some((action, value)) = parser.entry()? {
+        let mut shortcuts = Vec::new();
+        let events = match value.get("events") {
+            Some(Variant::Array(events)) => events.as_slice(),
+            _ => &[],
+        };
+        for event in events {
+            let mut report = |problem| {
+                import.issues.push(GodotIssue {
+                    action: action.clone(),
+                    problem,
+                })
+            };
+            match event {
+                Variant::Object { class, .. } if class == "InputEventKey" => {
+                    match key_event(event, &mut report) {
+                        Some(shortcut) if !shortcuts.contains(&shortcut) => {
+                            shortcuts.push(shortcut)
+                        }
+                        _ => {}
+                    }
+                }
+                Variant::Object { class, .. } => {
+                    report(GodotProblem::UnsupportedEvent(class.clone()))
+                }
+                _ => report(GodotProblem::UnsupportedEvent("non-object".into())),
+            }
+        }
+        import.keymap.insert(
+            action,
+            Shortcuts {
+                shortcuts,
+                ..Shortcuts::default()
+            },
+        );
+    }
+    Ok(import)
+}
+
+fn key_event(event: &Variant, report: &mut impl FnMut(GodotProblem)) -> Option<Shortcut> {
+    let number = |property| event.get(property).and_then(Variant::as_u32).unwrap_or(0);
+    let pressed = |property| {
+        matches!(event.get(property), Some(Variant::Bool(true)))
+            .then_some(ModifierType::RequirePressed)
+    };
+
+    let code = match (number("physical_keycode"), number("keycode")) {
+        (0, 0) => {
+            report(GodotProblem::UnknownGodotKey(0));
+            return None;
+        }
+        (0, logical) => {
+            report(GodotProblem::LogicalKeycode);
+            logical
+        }
+        (physical, _) => physical,
+    };
+    let Some(key) = key_from_godot(code, number("location")) else {
+        report(GodotProblem::UnknownGodotKey(code));
+        return None;
+    };
+    Some(Shortcut {
+        input: key.into(),
+        modifiers: Modifiers::new(
+            pressed("ctrl_pressed"),
+            pressed("alt_pressed"),
+            pressed("shift_pressed"),
+            pressed("meta_pressed"),
+        ),
+    })
+}
+
+/// Writes `keymap` as a Godot 4 `[input]` section.
+///
+/// Each alternative becomes an `InputEventKey` on its physical key. Godot
+/// has no way to forbid a modifier or to express a [`Trigger`], so those are
+/// dropped and reported.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy::prelude::KeyCode;
+/// use bevy_keyboard_shortcuts::{Keymap, Shortcuts, export_godot_input, import_godot_input};
+///
+/// let keymap = Keymap::from_iter([("jump", Shortcuts::single_press(&[KeyCode::Space]))]);
+/// let export = export_godot_input(&keymap);
+/// assert!(export.section.starts_with("[input]\n\njump={\n"));
+/// assert_eq!(import_godot_input(&export.section).unwrap().keymap, keymap);
+/// ```
+pub fn export_godot_input(keymap: &Keymap) -> GodotExport {
+    let mut export = GodotExport {
+        section: "[input]\n\n".into(),
+        issues: Vec::new(),
+    };
+    for (action, shortcuts) in keymap {
+        let mut report = |problem| {
+            export.issues.push(GodotIssue {
+                action: action.clone(),
+                problem,
+            })
+        };
+        if shortcuts.trigger != Trigger::Press {
+            report(GodotProblem::Trigger(shortcuts.trigger));
+        }
+        if shortcuts.repeats {
+            report(GodotProblem::Repeating);
+        }
+
+        let mut events = Vec::new();
+        for shortcut in shortcuts.iter() {
+            let Some(key) = shortcut.input.key() else {
+                report(GodotProblem::UnsupportedInput(shortcut.input));
+                continue;
+            };
+            let Some((code, location)) = key_to_godot(key) else {
+                report(GodotProblem::UnknownKey(key));
+                continue;
+            };
+            let modifiers = &shortcut.modifiers;
+            let all = [
+                &modifiers.control,
+                &modifiers.alt,
+                &modifiers.shift,
+                &modifiers.super_key,
+            ];
+            if all.contains(&&Some(ModifierType::RequireNotPressed)) {
+                report(GodotProblem::ModifierExclusion);
+            }
+            let pressed = |m: &Option<ModifierType>| *m == Some(ModifierType::RequirePressed);
+            events.push(format!(
+                "Object(InputEventKey,\"resource_local_to_scene\":false,\"resource_name\":\"\",\
+                 \"device\":-1,\"window_id\":0,\"alt_pressed\":{},\"shift_pressed\":{},\
+                 \"ctrl_pressed\":{},\"meta_pressed\":{},\"pressed\":false,\"keycode\":0,\
+                 \"physical_keycode\":{code},\"key_label\":0,\"unicode\":0,\
+                 \"location\":{location},\"echo\":false,\"script\":null)\n",
+                pressed(&modifiers.alt),
+                pressed(&modifiers.shift),
+                pressed(&modifiers.control),
+                pressed(&modifiers.super_key),
+            ));
+        }
+
+        export.section += &format!(
+            "{}={{\n\"deadzone\": 0.5,\n\"events\": [{}]\n}}\n",
+            godot_key_name(action),
+            events.join(", ")
+        );
+    }
+    export
+}
+
+/// Action names are written bare when Godot would, quoted otherwise.
+fn godot_key_name(action: &str) -> String {
+    let bare = !action.is_empty()
+        && action
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '/' | '.' | '-'));
+    if bare {
+        action.to_string()
+    } else {
+        format!("\"{}\"", action.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+/// Godot's `Key::SPECIAL` bit, set on every non-printable key code.
+const SPECIAL: u32 = 1 << 22;
+
+/// The Godot 4 physical key code of `key`, and the `location` that tells
+/// left and right modifier keys apart (0 for other keys).
+fn key_to_godot(key: KeyCode) -> Option<(u32, u32)> {
+    let name = format!("{key:?}");
+    let suffix = |prefix: &str| name.strip_prefix(prefix).filter(|rest| !rest.is_empty());
+
+    // Printable keys are their US-layout ASCII code.
+    if let Some(letter) = suffix("Key").filter(|l| l.len() == 1) {
+        return Some((letter.as_bytes()[0] as u32, 0));
+    }
+    if let Some(digit) = suffix("Digit") {
+        return Some((digit.as_bytes()[0] as u32, 0));
+    }
+    if let Some(n) = suffix("Numpad").and_then(|n| n.parse::<u32>().ok()) {
+        return Some((SPECIAL | (0x86 + n), 0));
+    }
+    if let Some(n) = suffix("F").and_then(|n| n.parse::<u32>().ok()) {
+        return Some((SPECIAL | (0x1B + n), 0));
+    }
+
+    let (code, location) = match key {
+        KeyCode::Space => (0x20, 0),
+        KeyCode::Quote => (0x27, 0),
+        KeyCode::Comma => (0x2C, 0),
+        KeyCode::Minus => (0x2D, 0),
+        KeyCode::Period => (0x2E, 0),
+        KeyCode::Slash => (0x2F, 0),
+        KeyCode::Semicolon => (0x3B, 0),
+        KeyCode::Equal => (0x3D, 0),
+        KeyCode::BracketLeft => (0x5B, 0),
+        KeyCode::Backslash => (0x5C, 0),
+        KeyCode::BracketRight => (0x5D, 0),
+        KeyCode::Backquote => (0x60, 0),
+        KeyCode::Escape => (SPECIAL | 0x01, 0),
+        KeyCode::Tab => (SPECIAL | 0x02, 0),
+        KeyCode::Backspace => (SPECIAL | 0x04, 0),
+        KeyCode::Enter => (SPECIAL | 0x05, 0),
+        KeyCode::NumpadEnter => (SPECIAL | 0x06, 0),
+        KeyCode::Insert => (SPECIAL | 0x07, 0),
+        KeyCode::Delete => (SPECIAL | 0x08, 0),
+        KeyCode::Pause => (SPECIAL | 0x09, 0),
+        KeyCode::PrintScreen => (SPECIAL | 0x0A, 0),
+        KeyCode::Home => (SPECIAL | 0x0D, 0),
+        KeyCode::End => (SPECIAL | 0x0E, 0),
+        KeyCode::ArrowLeft => (SPECIAL | 0x0F, 0),
+        KeyCode::ArrowUp => (SPECIAL | 0x10, 0),
+        KeyCode::ArrowRight => (SPECIAL | 0x11, 0),
+        KeyCode::ArrowDown => (SPECIAL | 0x12, 0),
+        KeyCode::PageUp => (SPECIAL | 0x13, 0),
+        KeyCode::PageDown => (SPECIAL | 0x14, 0),
+        KeyCode::ShiftLeft => (SPECIAL | 0x15, 1),
+        KeyCode::ShiftRight => (SPECIAL | 0x15, 2),
+        KeyCode::ControlLeft => (SPECIAL | 0x16, 1),
+        KeyCode::ControlRight => (SPECIAL | 0x16, 2),
+        KeyCode::SuperLeft => (SPECIAL | 0x17, 1),
+        KeyCode::SuperRight => (SPECIAL | 0x17, 2),
+        KeyCode::AltLeft => (SPECIAL | 0x18, 1),
+        KeyCode::AltRight => (SPECIAL | 0x18, 2),
+        KeyCode::CapsLock => (SPECIAL | 0x19, 0),
+        KeyCode::NumLock => (SPECIAL | 0x1A, 0),
+        KeyCode::ScrollLock => (SPECIAL | 0x1B, 0),
+        KeyCode::ContextMenu => (SPECIAL | 0x42, 0),
+        KeyCode::NumpadMultiply => (SPECIAL | 0x81, 0),
+        KeyCode::NumpadDivide => (SPECIAL | 0x82, 0),
+        KeyCode::NumpadSubtract => (SPECIAL | 0x83, 0),
+        KeyCode::NumpadDecimal => (SPECIAL | 0x84, 0),
+        KeyCode::NumpadAdd => (SPECIAL | 0x85, 0),
+        _ => return None,
+    };
+    Some((code, location))
+}
+
+/// The key with Godot 4 physical key code `code`. A modifier key with no
+/// `location` is taken as the left one.
+fn key_from_godot(code: u32, location: u32) -> Option<KeyCode> {
+    let side = if location == 2 { 2 } else { 1 };
+    key_code_names()
+        .filter_map(|name| serde_json::from_value::<KeyCode>(name.into()).ok())
+        .filter_map(|key| key_to_godot(key).map(|godot| (key, godot)))
+        .filter(|&(_, (c, l))| c == code && (l == 0 || l == side))
+        .map(|(key, _)| key)
+        .next()
+}
+
+/// A value in Godot's text resource syntax, as far as `[input]` needs it.
+#[derive(Debug, Clone, PartialEq)]
+enum Variant {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Variant>),
+    /// Dictionary entries, or an `Object`'s properties
+    Dictionary(Vec<(String, Variant)>),
+    Object {
+        class: String,
+        properties: Vec<(String, Variant)>,
+    },
+    /// Any other constructor, such as `Vector2(0, 0)`
+    Constructor(String, Vec<Variant>),
+}
+
+impl Variant {
+    /// A dictionary entry or object property.
+    fn get(&self, key: &str) -> Option<&Variant> {
+        match self {
+            Variant::Dictionary(entries)
+            | Variant::Object {
+                properties: entries,
+                ..
+            } => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_u32(&self) -> Option<u32> {
+        match self {
+            Variant::Number(n) if *n >= 0.0 && *n <= u32::MAX as f64 => Some(*n as u32),
+            _ => None,
+        }
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Self {
+        Self { text, pos: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn error(&self, message: impl Into<String>) -> GodotParseError {
+        let before = &self.text[..self.pos];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        GodotParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message: message.into(),
+        }
+    }
+
+    /// Skips whitespace and `;` comment lines.
+    fn skip_space(&mut self) {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+            if trimmed.starts_with(';') {
+                self.pos += trimmed.find('\n').unwrap_or(trimmed.len());
+            } else {
+                return;
+            }
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), GodotParseError> {
+        self.skip_space();
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            Ok(())
+        } else {
+            Err(self.error(format!("expected {c:?}")))
+        }
+    }
+
+    /// Moves past the `[name]` header line. Returns `false` if there is none.
+    fn seek_section(&mut self, name: &str) -> bool {
+        let header = format!("[{name}]");
+        let mut offset = 0;
+        for line in self.text.split_inclusive('\n') {
+            offset += line.len();
+            if line.trim() == header {
+                self.pos = offset;
+                return true;
+            }
+        }
+        false
+    }
+
+    /// The next `key=value` of the section, or `None` at its end.
+    fn entry(&mut self) -> Result<Option<(String, Variant)>, GodotParseError> {
+        self.skip_space();
+        let key = match self.peek() {
+            None | Some('[') => return Ok(None),
+            Some('"') => self.string()?,
+            Some(_) => {
+                let rest = self.rest();
+                let end = rest
+                    .find(['=', '\n'])
+                    .ok_or_else(|| self.error("expected \"=\""))?;
+                self.pos += end;
+                rest[..end].trim().to_string()
+            }
+        };
+        self.expect('=')?;
+        Ok(Some((key, self.value()?)))
+    }
+
+    fn value(&mut self) -> Result<Variant, GodotParseError> {
+        self.skip_space();
+        match self.peek() {
+            Some('{') => {
+                self.pos += 1;
+                let entries = self.list('}', |parser| {
+                    let key = match parser.value()? {
+                        Variant::String(key) => key,
+                        _ => return Err(parser.error("expected a string key")),
+                    };
+                    parser.expect(':')?;
+                    Ok((key, parser.value()?))
+                })?;
+                Ok(Variant::Dictionary(entries))
+            }
+            Some('[') => {
+                self.pos += 1;
+                Ok(Variant::Array(self.list(']', Self::value)?))
+            }
+            Some('"') => Ok(Variant::String(self.string()?)),
+            // StringName and NodePath literals.
+            Some('&' | '^') => {
+                self.pos += 1;
+                Ok(Variant::String(self.string()?))
+            }
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                let rest = self.rest();
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.')))
+                    .unwrap_or(rest.len());
+                let number = rest[..end]
+                    .parse()
+                    .map_err(|_| self.error(format!("invalid number {:?}", &rest[..end])))?;
+                self.pos += end;
+                Ok(Variant::Number(number))
+            }
+            Some(c) if c.is_ascii_alphabetic() => {
+                let rest = self.rest();
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                let name = &rest[..end];
+                self.pos += end;
+                match name {
+                    "true" => return Ok(Variant::Bool(true)),
+                    "false" => return Ok(Variant::Bool(false)),
+                    "null" | "nil" => return Ok(Variant::Null),
+                    _ => {}
+                }
+                self.expect('(')?;
+                if name == "Object" {
+                    self.skip_space();
+                    let rest = self.rest();
+                    let end = rest.find([',', ')']).unwrap_or(rest.len());
+                    let class = rest[..end].trim().to_string();
+                    self.pos += end;
+                    if self.peek() == Some(',') {
+                        self.pos += 1;
+                    }
+                    let properties = self.list(')', |parser| {
+                        let key = parser.string_at()?;
+                        parser.expect(':')?;
+                        Ok((key, parser.value()?))
+                    })?;
+                    Ok(Variant::Object { class, properties })
+                } else {
+                    Ok(Variant::Constructor(
+                        name.to_string(),
+                        self.list(')', Self::value)?,
+                    ))
+                }
+            }
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    /// Comma-separated items up to `close`, which has not been consumed yet.
+    fn list<T>(
+        &mut self,
+        close: char,
+        mut item: impl FnMut(&mut Self) -> Result<T, GodotParseError>,
+    ) -> Result<Vec<T>, GodotParseError> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_space();
+            if self.peek() == Some(close) {
+                self.pos += 1;
+                return Ok(items);
+            }
+            if !items.is_empty() {
+                self.expect(',')?;
+                self.skip_space();
+                if self.peek() == Some(close) {
+                    self.pos += 1;
+                    return Ok(items);
+                }
+            }
+            items.push(item(self)?);
+        }
+    }
+
+    fn string_at(&mut self) -> Result<String, GodotParseError> {
+        self.skip_space();
+        self.string()
+    }
+
+    fn string(&mut self) -> Result<String, GodotParseError> {
+        if self.peek() != Some('"') {
+            return Err(self.error("expected a string"));
+        }
+        let start = self.pos;
+        self.pos += 1;
+        let mut out = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Ok(out);
+                }
+                '\\' => match chars.next() {
+                    Some((_, 'n')) => out.push('\n'),
+                    Some((_, 't')) => out.push('\t'),
+                    Some((_, escaped)) => out.push(escaped),
+                    None => break,
+                },
+                c => out.push(c),
+            }
+        }
+        self.pos = start;
+        Err(self.error("unterminated string"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROJECT: &str = include_str!("../fixtures/godot/project.godot");
+    const EXPORTED: &str = include_str!("../fixtures/godot/input.godot");
+
+    #[test]
+    fn key_codes_round_trip_for_every_mapped_key() {
+        for key in key_code_names().filter_map(|n| serde_json::from_value::<KeyCode>(n.into()).ok())
+        {
+            if let Some((code, location)) = key_to_godot(key) {
+                assert_eq!(key_from_godot(code, location), Some(key), "{key:?}");
+            }
+        }
+        assert_eq!(key_to_godot(KeyCode::KeyA), Some((65, 0)));
+        assert_eq!(key_to_godot(KeyCode::F1), Some((4194332, 0)));
+        assert_eq!(key_from_godot(SPECIAL | 0x15, 0), Some(KeyCode::ShiftLeft));
+    }
+
+    #[test]
+    fn a_project_file_imports_its_key_events() {
+        let import = import_godot_input(PROJECT).unwrap();
+        let binding = |action: &str| import.keymap.get(action).unwrap().to_string();
+
+        assert_eq!(binding("move_left"), "A, Left");
+        assert_eq!(binding("quick_save"), "Ctrl + S");
+        let crouch = &import.keymap.get("crouch").unwrap().shortcuts[0];
+        assert_eq!(crouch.input, KeyCode::ShiftRight.into());
+        assert_eq!(binding("shoot"), "", "mouse only");
+        assert_eq!(
+            import.issues,
+            [
+                GodotIssue {
+                    action: "pause".into(),
+                    problem: GodotProblem::UnsupportedEvent("InputEventJoypadButton".into())
+                },
+                GodotIssue {
+                    action: "shoot".into(),
+                    problem: GodotProblem::UnsupportedEvent("InputEventMouseButton".into())
+                },
+                GodotIssue {
+                    action: "toggle_map".into(),
+                    problem: GodotProblem::LogicalKeycode
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn export_matches_the_fixture_and_imports_back() {
+        let keymap = import_godot_input(PROJECT).unwrap().keymap;
+
+        let export = export_godot_input(&keymap);
+        assert_eq!(export.section, EXPORTED);
+        assert!(export.issues.is_empty());
+
+        let reimported = import_godot_input(&export.section).unwrap();
+        assert_eq!(reimported.keymap, keymap);
+        assert!(reimported.issues.is_empty());
+    }
+
+    #[test]
+    fn unrepresentable_bindings_are_reported_on_export() {
+        let keymap = Keymap::from_iter([
+            (
+                "dash",
+                Shortcuts::multi_tap(&[KeyCode::KeyD], 2, std::time::Duration::from_millis(250)),
+            ),
+            (
+                "redo",
+                Shortcuts::single_press(&[KeyCode::KeyZ])
+                    .with_ctrl()
+                    .without_shift(),
+            ),
+            ("launch", Shortcuts::single_press(&[KeyCode::LaunchApp1])),
+            ("walk", Shortcuts::repeating(&[KeyCode::KeyW])),
+        ]);
+
+        let problems: Vec<_> = export_godot_input(&keymap)
+            .issues
+            .into_iter()
+            .map(|issue| issue.problem)
+            .collect();
+
+        assert_eq!(
+            problems,
+            [
+                GodotProblem::Trigger(keymap.get("dash").unwrap().trigger),
+                GodotProblem::UnknownKey(KeyCode::LaunchApp1),
+                GodotProblem::ModifierExclusion,
+                GodotProblem::Repeating,
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_errors_carry_line_and_column() {
+        let error = import_godot_input(
+            "[input]\n\njump={\n\"events\": [Object(InputEventKey,\"pressed\" false)]\n}\n",
+        )
+        .unwrap_err();
+        assert_eq!((error.line, error.column), (4, 43));
+    }
+}
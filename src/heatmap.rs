@@ -0,0 +1,583 @@
+//! Keyboard pictures of which actions sit on which keys.
+
+use crate::schema::key_code_names;
+use crate::{InputButton, Keymap};
+use bevy::input::keyboard::KeyCode;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// One action bound to a key, as drawn on a [`KeyboardHeatmap`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeatmapBinding {
+    /// The context (keymap) it came from, numbered from 0 in the order the
+    /// contexts were added
+    pub context: usize,
+    /// The bound action
+    pub action: String,
+    /// The modifiers that must be held, as
+    /// [`Modifiers::required_names`](crate::Modifiers::required_names)
+    /// lists them; empty for the base layer
+    pub layer: Vec<&'static str>,
+}
+
+/// Which actions are bound to each key, across one or more keymaps, drawn on
+/// a US ANSI keyboard.
+///
+/// Each keymap is a *context* — "Editor", "Game", "Menus" — and gets its own
+/// hue. In the SVG, a key is split into one band per binding, and the band is
+/// darker the fewer modifiers its layer needs: a dark band is a bare key
+/// press, a pale one a `Ctrl + Shift` chord. The ASCII picture numbers the
+/// contexts instead, and lists every binding below the keyboard. Keys off the
+/// drawn layout (the numpad, media keys) appear only in the lists, and mouse
+/// bindings are left out.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy::prelude::KeyCode;
+/// use bevy_keyboard_shortcuts::{KeyboardHeatmap, Keymap, Shortcuts};
+///
+/// let editor = Keymap::from_iter([
+///     ("save", Shortcuts::single_press(&[KeyCode::KeyS]).with_ctrl()),
+///     ("select", Shortcuts::single_press(&[KeyCode::KeyV])),
+/// ]);
+/// let game = Keymap::from_iter([("move_down", Shortcuts::repeating(&[KeyCode::KeyS]))]);
+///
+/// let heatmap = KeyboardHeatmap::new()
+///     .context("Editor", &editor)
+///     .context("Game", &game);
+///
+/// assert_eq!(heatmap.bindings(KeyCode::KeyS).len(), 2);
+/// assert!(heatmap.to_ascii().contains("S  [1] Ctrl: save, [2] move_down"));
+/// assert!(heatmap.to_svg().starts_with("<svg"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct KeyboardHeatmap {
+    contexts: Vec<String>,
+    keys: HashMap<KeyCode, Vec<HeatmapBinding>>,
+}
+
+impl KeyboardHeatmap {
+    /// Creates a heatmap with no bindings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds every binding of `keymap` under the context `name` (chainable).
+    pub fn context(mut self, name: impl Into<String>, keymap: &Keymap) -> Self {
+        let context = self.contexts.len();
+        self.contexts.push(name.into());
+        for (action, shortcuts) in keymap {
+            // Only keys have a place on the drawn keyboard.
+            for shortcut in shortcuts.iter() {
+                let Some(key) = shortcut.input.key() else {
+                    continue;
+                };
+                self.keys.entry(key).or_default().push(HeatmapBinding {
+                    context,
+                    action: action.clone(),
+                    layer: shortcut.modifiers.required_names(),
+                });
+            }
+        }
+        self
+    }
+
+    /// The context names, in the order they were added.
+    pub fn contexts(&self) -> &[String] {
+        &self.contexts
+    }
+
+    /// Everything bound to `key`, by context, then by action name.
+    pub fn bindings(&self, key: KeyCode) -> &[HeatmapBinding] {
+        self.keys.get(&key).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// The keyboard as box drawing, marked with the numbers of the contexts
+    /// using each key, followed by a legend and every binding by key.
+    pub fn to_ascii(&self) -> String {
+        let height = LAYOUT.len() * 3 + 1;
+        let width = ascii_x(LAYOUT_WIDTH) + 1;
+        let mut grid = vec![vec![' '; width]; height];
+
+        for (row, keys) in LAYOUT.iter().enumerate() {
+            for &(key, x, w) in *keys {
+                let (left, right, top) = (ascii_x(x), ascii_x(x + w), row * 3);
+                for (line, cells) in grid.iter_mut().enumerate().skip(top).take(4) {
+                    let edge_row = line == top || line == top + 3;
+                    for (col, cell) in cells.iter_mut().enumerate().take(right + 1).skip(left) {
+                        let edge_col = col == left || col == right;
+                        let c = match (edge_row, edge_col) {
+                            (true, true) => '+',
+                            (true, false) => '-',
+                            (false, true) => '|',
+                            (false, false) => continue,
+                        };
+                        // Neighbouring keys share borders; corners win.
+                        if *cell != '+' {
+                            *cell = c;
+                        }
+                    }
+                }
+
+                let inner = right - left - 1;
+                let mut contexts: Vec<usize> =
+                    self.bindings(key).iter().map(|b| b.context).collect();
+                contexts.dedup();
+                let marks: String = contexts.iter().map(|&c| context_mark(c)).collect();
+                for (line, text) in [(top + 1, key_label(key)), (top + 2, marks)] {
+                    let text: String = text.chars().take(inner).collect();
+                    let start = left + 1 + (inner - text.chars().count()) / 2;
+                    for (i, c) in text.chars().enumerate() {
+                        grid[line][start + i] = c;
+                    }
+                }
+            }
+        }
+
+        let mut out: String = grid
+            .into_iter()
+            .map(|line| line.into_iter().collect::<String>().trim_end().to_string() + "\n")
+            .collect();
+
+        out += "\n";
+        let legend: Vec<String> = self
+            .contexts
+            .iter()
+            .enumerate()
+            .map(|(i, name)| format!("[{}] {name}", context_mark(i)))
+            .collect();
+        let _ = writeln!(out, "{}", legend.join("  "));
+
+        let listed = self.bound_keys();
+        let label_width = listed
+            .iter()
+            .map(|&key| key_label(key).chars().count())
+            .max()
+            .unwrap_or(0);
+        if !listed.is_empty() {
+            out += "\n";
+        }
+        for key in listed {
+            let bindings: Vec<String> = self
+                .bindings(key)
+                .iter()
+                .map(|binding| {
+                    let layer = binding.layer.join(" + ");
+                    let layer = if layer.is_empty() {
+                        layer
+                    } else {
+                        layer + ": "
+                    };
+                    format!(
+                        "[{}] {layer}{}",
+                        context_mark(binding.context),
+                        binding.action
+                    )
+                })
+                .collect();
+            let _ = writeln!(
+                out,
+                "{:label_width$}  {}",
+                key_label(key),
+                bindings.join(", ")
+            );
+        }
+        out
+    }
+
+    /// The keyboard as a standalone SVG image, with a legend of the contexts
+    /// and layers, and a list of the bound keys it does not draw. Hovering a
+    /// key shows all its bindings.
+    pub fn to_svg(&self) -> String {
+        let keyboard_height = LAYOUT.len() as u32 * SVG_UNIT;
+        let off_layout: Vec<KeyCode> = self
+            .bound_keys()
+            .into_iter()
+            .filter(|&key| !on_layout(key))
+            .collect();
+        let legend_top = keyboard_height + 16;
+        let lists_top = legend_top + 2 * 18 + 8;
+        let width = LAYOUT_WIDTH * SVG_UNIT / 4 + 16;
+        let height = lists_top + off_layout.len() as u32 * 16 + 8;
+
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {width} {height}\" font-family=\"system-ui, sans-serif\">\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"#ffffff\"/>\n"
+        );
+
+        for (row, keys) in LAYOUT.iter().enumerate() {
+            for &(key, x, w) in *keys {
+                let (x, y) = (8 + x * SVG_UNIT / 4, 8 + row as u32 * SVG_UNIT);
+                let (w, h) = (w * SVG_UNIT / 4 - 4, SVG_UNIT - 4);
+                let bindings = self.bindings(key);
+                let _ = writeln!(
+                    out,
+                    "<g>\n<title>{}</title>",
+                    xml_escape(&self.tooltip(key))
+                );
+                let _ = writeln!(
+                    out,
+                    "<rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\" rx=\"4\" \
+                     fill=\"#f4f4f4\" stroke=\"#999999\"/>"
+                );
+
+                // Bands fill the key below its label.
+                let band_top = y + 14;
+                let band_height = (h - 16) as f32 / bindings.len().max(1) as f32;
+                for (i, binding) in bindings.iter().enumerate() {
+                    let band_y = band_top as f32 + i as f32 * band_height;
+                    let _ = writeln!(
+                        out,
+                        "<rect x=\"{}\" y=\"{band_y:.1}\" width=\"{}\" height=\"{band_height:.1}\" \
+                         fill=\"{}\"/>",
+                        x + 2,
+                        w - 4,
+                        band_colour(binding.context, binding.layer.len()),
+                    );
+                    if band_height >= 9.0 {
+                        let text = if binding.layer.is_empty() {
+                            "#ffffff"
+                        } else {
+                            "#111111"
+                        };
+                        let _ = writeln!(
+                            out,
+                            "<text x=\"{}\" y=\"{:.1}\" font-size=\"8\" fill=\"{text}\">{}</text>",
+                            x + 4,
+                            band_y + band_height / 2.0 + 3.0,
+                            xml_escape(&binding.action),
+                        );
+                    }
+                }
+                let _ = writeln!(
+                    out,
+                    "<text x=\"{}\" y=\"{}\" font-size=\"10\" fill=\"#333333\">{}</text>\n</g>",
+                    x + 4,
+                    y + 11,
+                    xml_escape(&key_label(key)),
+                );
+            }
+        }
+
+        let mut legend_x = 8;
+        for (i, name) in self.contexts.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "<rect x=\"{legend_x}\" y=\"{legend_top}\" width=\"12\" height=\"12\" fill=\"{}\"/>\n\
+                 <text x=\"{}\" y=\"{}\" font-size=\"12\">{}</text>",
+                band_colour(i, 0),
+                legend_x + 16,
+                legend_top + 10,
+                xml_escape(name),
+            );
+            legend_x += 32 + 7 * name.chars().count() as u32;
+        }
+        for (layers, text) in [(0, "no modifiers"), (1, "one modifier"), (2, "two or more")] {
+            let (x, y) = (8 + layers as u32 * 120, legend_top + 18);
+            let _ = writeln!(
+                out,
+                "<rect x=\"{x}\" y=\"{y}\" width=\"12\" height=\"12\" fill=\"{}\"/>\n\
+                 <text x=\"{}\" y=\"{}\" font-size=\"12\">{text}</text>",
+                layer_grey(layers),
+                x + 16,
+                y + 10,
+            );
+        }
+
+        for (i, &key) in off_layout.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "<text x=\"8\" y=\"{}\" font-size=\"12\">{}</text>",
+                lists_top + i as u32 * 16 + 12,
+                xml_escape(&self.tooltip(key)),
+            );
+        }
+        out += "</svg>\n";
+        out
+    }
+
+    /// Every key with a binding: the drawn layout first, in reading order,
+    /// then the rest in `KeyCode` order.
+    fn bound_keys(&self) -> Vec<KeyCode> {
+        let drawn = LAYOUT
+            .iter()
+            .flat_map(|row| row.iter().map(|&(key, _, _)| key));
+        let rest = key_code_names()
+            .filter_map(|name| serde_json::from_value::<KeyCode>(name.into()).ok())
+            .filter(|&key| !on_layout(key));
+        drawn
+            .chain(rest)
+            .filter(|&key| !self.bindings(key).is_empty())
+            .collect()
+    }
+
+    /// `S: Editor Ctrl + save; Game move_down`
+    fn tooltip(&self, key: KeyCode) -> String {
+        let bindings: Vec<String> = self
+            .bindings(key)
+            .iter()
+            .map(|binding| {
+                let mut text = self.contexts[binding.context].clone();
+                for modifier in &binding.layer {
+                    let _ = write!(text, " {modifier} +");
+                }
+                text + " " + &binding.action
+            })
+            .collect();
+        format!("{}: {}", key_label(key), bindings.join("; "))
+    }
+}
+
+/// Keys of a US ANSI keyboard with its navigation cluster, by row, as
+/// `(key, x, width)` in quarters of a key.
+const LAYOUT: &[&[(KeyCode, u32, u32)]] = {
+    use KeyCode::*;
+    &[
+        &[
+            (Escape, 0, 4),
+            (F1, 8, 4),
+            (F2, 12, 4),
+            (F3, 16, 4),
+            (F4, 20, 4),
+            (F5, 26, 4),
+            (F6, 30, 4),
+            (F7, 34, 4),
+            (F8, 38, 4),
+            (F9, 44, 4),
+            (F10, 48, 4),
+            (F11, 52, 4),
+            (F12, 56, 4),
+            (PrintScreen, 62, 4),
+            (ScrollLock, 66, 4),
+            (Pause, 70, 4),
+        ],
+        &[
+            (Backquote, 0, 4),
+            (Digit1, 4, 4),
+            (Digit2, 8, 4),
+            (Digit3, 12, 4),
+            (Digit4, 16, 4),
+            (Digit5, 20, 4),
+            (Digit6, 24, 4),
+            (Digit7, 28, 4),
+            (Digit8, 32, 4),
+            (Digit9, 36, 4),
+            (Digit0, 40, 4),
+            (Minus, 44, 4),
+            (Equal, 48, 4),
+            (Backspace, 52, 8),
+            (Insert, 62, 4),
+            (Home, 66, 4),
+            (PageUp, 70, 4),
+        ],
+        &[
+            (Tab, 0, 6),
+            (KeyQ, 6, 4),
+            (KeyW, 10, 4),
+            (KeyE, 14, 4),
+            (KeyR, 18, 4),
+            (KeyT, 22, 4),
+            (KeyY, 26, 4),
+            (KeyU, 30, 4),
+            (KeyI, 34, 4),
+            (KeyO, 38, 4),
+            (KeyP, 42, 4),
+            (BracketLeft, 46, 4),
+            (BracketRight, 50, 4),
+            (Backslash, 54, 6),
+            (Delete, 62, 4),
+            (End, 66, 4),
+            (PageDown, 70, 4),
+        ],
+        &[
+            (CapsLock, 0, 7),
+            (KeyA, 7, 4),
+            (KeyS, 11, 4),
+            (KeyD, 15, 4),
+            (KeyF, 19, 4),
+            (KeyG, 23, 4),
+            (KeyH, 27, 4),
+            (KeyJ, 31, 4),
+            (KeyK, 35, 4),
+            (KeyL, 39, 4),
+            (Semicolon, 43, 4),
+            (Quote, 47, 4),
+            (Enter, 51, 9),
+        ],
+        &[
+            (ShiftLeft, 0, 9),
+            (KeyZ, 9, 4),
+            (KeyX, 13, 4),
+            (KeyC, 17, 4),
+            (KeyV, 21, 4),
+            (KeyB, 25, 4),
+            (KeyN, 29, 4),
+            (KeyM, 33, 4),
+            (Comma, 37, 4),
+            (Period, 41, 4),
+            (Slash, 45, 4),
+            (ShiftRight, 49, 11),
+            (ArrowUp, 66, 4),
+        ],
+        &[
+            (ControlLeft, 0, 5),
+            (SuperLeft, 5, 5),
+            (AltLeft, 10, 5),
+            (Space, 15, 25),
+            (AltRight, 40, 5),
+            (SuperRight, 45, 5),
+            (ContextMenu, 50, 5),
+            (ControlRight, 55, 5),
+            (ArrowLeft, 62, 4),
+            (ArrowDown, 66, 4),
+            (ArrowRight, 70, 4),
+        ],
+    ]
+};
+
+/// The width of [`LAYOUT`], in quarters of a key.
+const LAYOUT_WIDTH: u32 = 74;
+
+/// The side of a key in the SVG, in pixels.
+const SVG_UNIT: u32 = 48;
+
+fn on_layout(key: KeyCode) -> bool {
+    LAYOUT
+        .iter()
+        .flat_map(|row| row.iter())
+        .any(|&(k, _, _)| k == key)
+}
+
+/// A key is six characters wide in the ASCII picture.
+fn ascii_x(quarters: u32) -> usize {
+    (quarters * 3 / 2) as usize
+}
+
+fn key_label(key: KeyCode) -> String {
+    InputButton::Key(key).to_string()
+}
+
+/// `1`–`9`, then `a`–`z`, then `*` for any further context.
+fn context_mark(context: usize) -> char {
+    match context {
+        0..9 => char::from(b'1' + context as u8),
+        9..35 => char::from(b'a' + (context - 9) as u8),
+        _ => '*',
+    }
+}
+
+/// Hues spread by the golden angle so neighbouring contexts differ.
+fn band_colour(context: usize, layers: usize) -> String {
+    let hue = (context as f32 * 137.5) % 360.0;
+    format!("hsl({hue:.0}, 65%, {}%)", layer_lightness(layers))
+}
+
+fn layer_grey(layers: usize) -> String {
+    format!("hsl(0, 0%, {}%)", layer_lightness(layers))
+}
+
+fn layer_lightness(layers: usize) -> u32 {
+    match layers {
+        0 => 40,
+        1 => 62,
+        _ => 80,
+    }
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Shortcuts;
+
+    fn heatmap() -> KeyboardHeatmap {
+        let editor = Keymap::from_iter([
+            (
+                "save",
+                Shortcuts::single_press(&[KeyCode::KeyS]).with_ctrl(),
+            ),
+            (
+                "undo",
+                Shortcuts::single_press(&[KeyCode::KeyZ]).with_ctrl(),
+            ),
+            ("sum", Shortcuts::single_press(&[KeyCode::NumpadAdd])),
+        ]);
+        let game = Keymap::from_iter([
+            (
+                "move_down",
+                Shortcuts::repeating(&[KeyCode::KeyS, KeyCode::ArrowDown]),
+            ),
+            ("jump", Shortcuts::single_press(&[KeyCode::Space])),
+        ]);
+        KeyboardHeatmap::new()
+            .context("Editor", &editor)
+            .context("Game", &game)
+    }
+
+    #[test]
+    fn bindings_are_collected_per_key() {
+        let heatmap = heatmap();
+
+        assert_eq!(
+            heatmap.bindings(KeyCode::KeyS),
+            [
+                HeatmapBinding {
+                    context: 0,
+                    action: "save".into(),
+                    layer: vec!["Ctrl"],
+                },
+                HeatmapBinding {
+                    context: 1,
+                    action: "move_down".into(),
+                    layer: vec![],
+                },
+            ]
+        );
+        assert!(heatmap.bindings(KeyCode::KeyQ).is_empty());
+        assert_eq!(heatmap.contexts(), ["Editor", "Game"]);
+    }
+
+    #[test]
+    fn ascii_marks_keys_and_lists_bindings() {
+        let ascii = heatmap().to_ascii();
+        let lines: Vec<&str> = ascii.lines().collect();
+
+        // The home row: CapsLock, then A, then S used by both contexts.
+        assert!(lines[10].starts_with("|CapsLock |  A  |  S  |  D  |"));
+        assert!(lines[11].starts_with("|         |     | 12  |     |"));
+        assert!(lines.iter().all(|line| line.chars().count() <= 112));
+
+        let legend = ascii.split_once("\n\n").unwrap().1;
+        assert_eq!(
+            legend,
+            "[1] Editor  [2] Game\n\
+             \n\
+             S      [1] Ctrl: save, [2] move_down\n\
+             Z      [1] Ctrl: undo\n\
+             Space  [2] jump\n\
+             Down   [2] move_down\n\
+             Num +  [1] sum\n"
+        );
+    }
+
+    #[test]
+    fn svg_bands_keys_by_context_and_lists_off_layout_keys() {
+        let svg = heatmap().to_svg();
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains("<title>S: Editor Ctrl + save; Game move_down</title>"));
+        // Editor's chord is a pale band of its hue; Game's bare key a dark one.
+        assert!(svg.contains(&band_colour(0, 1)));
+        assert!(svg.contains(&band_colour(1, 0)));
+        assert!(svg.contains(">Num +: Editor sum</text>"));
+    }
+}
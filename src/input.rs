@@ -0,0 +1,618 @@
+//! What a shortcut is bound to, and the button state it is matched against.
+
+#[cfg(feature = "mouse")]
+use crate::WheelDirection;
+#[cfg(feature = "gamepad")]
+use crate::gamepad::GamepadGlyphs;
+use crate::{KEY_DISPLAY_MAP, ModifierType, Modifiers, Shortcut};
+use bevy::input::ButtonInput;
+#[cfg(feature = "gamepad")]
+use bevy::input::gamepad::{Gamepad, GamepadButton};
+use bevy::input::keyboard::KeyCode;
+#[cfg(feature = "mouse")]
+use bevy::input::mouse::MouseButton;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+
+/// The key or button a [`Shortcut`] is bound to.
+///
+/// Keymap files name it with a `key` field (`key: KeyS`) or, with the `mouse`
+/// feature, a `mouse` field (`mouse: Left`) or a `wheel` field (`wheel: Up`).
+/// With the `gamepad` feature it may be a `gamepad` field (`gamepad: South`).
+///
+/// Non-exhaustive, since each of those features adds variants.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum InputButton {
+    /// A keyboard key
+    Key(KeyCode),
+    /// A mouse button
+    #[cfg(feature = "mouse")]
+    Mouse(MouseButton),
+    /// A notch of the mouse wheel. Never pressed or released: see
+    /// [`Shortcuts::scrolled`](crate::Shortcuts::scrolled).
+    #[cfg(feature = "mouse")]
+    Wheel(WheelDirection),
+    /// A gamepad button, on whichever gamepad the shortcut is checked against
+    #[cfg(feature = "gamepad")]
+    Gamepad(GamepadButton),
+}
+
+impl InputButton {
+    /// The key, if this is a keyboard key.
+    pub fn key(self) -> Option<KeyCode> {
+        match self {
+            InputButton::Key(key) => Some(key),
+            #[cfg(feature = "mouse")]
+            InputButton::Mouse(_) | InputButton::Wheel(_) => None,
+            #[cfg(feature = "gamepad")]
+            InputButton::Gamepad(_) => None,
+        }
+    }
+
+    /// The mouse button, if this is one.
+    #[cfg(feature = "mouse")]
+    pub fn mouse_button(self) -> Option<MouseButton> {
+        match self {
+            InputButton::Mouse(button) => Some(button),
+            _ => None,
+        }
+    }
+
+    /// The wheel direction, if this is one.
+    #[cfg(feature = "mouse")]
+    pub fn wheel_direction(self) -> Option<WheelDirection> {
+        match self {
+            InputButton::Wheel(direction) => Some(direction),
+            _ => None,
+        }
+    }
+
+    /// The gamepad button, if this is one.
+    #[cfg(feature = "gamepad")]
+    pub fn gamepad_button(self) -> Option<GamepadButton> {
+        match self {
+            InputButton::Gamepad(button) => Some(button),
+            _ => None,
+        }
+    }
+}
+
+impl From<KeyCode> for InputButton {
+    fn from(key: KeyCode) -> Self {
+        InputButton::Key(key)
+    }
+}
+
+#[cfg(feature = "mouse")]
+impl From<MouseButton> for InputButton {
+    fn from(button: MouseButton) -> Self {
+        InputButton::Mouse(button)
+    }
+}
+
+#[cfg(feature = "gamepad")]
+impl From<GamepadButton> for InputButton {
+    fn from(button: GamepadButton) -> Self {
+        InputButton::Gamepad(button)
+    }
+}
+
+/// The name a UI shows: `A` for `KeyA`, `Left` for `ArrowLeft`, `LMB` for
+/// the left mouse button. Gamepad buttons are named as on an Xbox
+/// controller; see `GamepadGlyphs` (`gamepad` feature) for the others.
+impl fmt::Display for InputButton {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputButton::Key(key) => {
+                let debug_str = format!("{key:?}");
+                match KEY_DISPLAY_MAP.get(debug_str.as_str()) {
+                    Some(name) => f.write_str(name),
+                    None => f.write_str(&debug_str),
+                }
+            }
+            #[cfg(feature = "mouse")]
+            InputButton::Mouse(button) => match button {
+                MouseButton::Left => f.write_str("LMB"),
+                MouseButton::Right => f.write_str("RMB"),
+                MouseButton::Middle => f.write_str("MMB"),
+                MouseButton::Back => f.write_str("Mouse4"),
+                MouseButton::Forward => f.write_str("Mouse5"),
+                MouseButton::Other(n) => write!(f, "Mouse{n}"),
+            },
+            #[cfg(feature = "mouse")]
+            InputButton::Wheel(direction) => write!(f, "Wheel {direction:?}"),
+            #[cfg(feature = "gamepad")]
+            InputButton::Gamepad(button) => f.write_str(&GamepadGlyphs::Xbox.name(*button)),
+        }
+    }
+}
+
+/// Keyboard state that shortcuts are matched against.
+///
+/// Implemented for Bevy's [`ButtonInput<KeyCode>`] and for [`KeySet`].
+/// Implement it for another backend, such as a winit event loop or a
+/// terminal, to match the same keymaps there.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy::prelude::KeyCode;
+/// use bevy_keyboard_shortcuts::{KeySet, Shortcuts};
+///
+/// let save = Shortcuts::single_press(&[KeyCode::KeyS]).with_ctrl();
+/// let mut keys = KeySet::default();
+/// keys.press(KeyCode::ControlLeft);
+/// keys.press(KeyCode::KeyS);
+/// assert!(save.pressed(&keys));
+/// ```
+pub trait KeyState {
+    /// Whether `key` is held down.
+    fn pressed(&self, key: KeyCode) -> bool;
+    /// Whether `key` went down this frame.
+    fn just_pressed(&self, key: KeyCode) -> bool;
+    /// Whether `key` came up this frame.
+    fn just_released(&self, key: KeyCode) -> bool;
+    /// Every key that went down this frame.
+    fn get_just_pressed(&self) -> impl Iterator<Item = KeyCode>;
+    /// Every key that came up this frame.
+    fn get_just_released(&self) -> impl Iterator<Item = KeyCode>;
+}
+
+impl KeyState for ButtonInput<KeyCode> {
+    fn pressed(&self, key: KeyCode) -> bool {
+        ButtonInput::pressed(self, key)
+    }
+
+    fn just_pressed(&self, key: KeyCode) -> bool {
+        ButtonInput::just_pressed(self, key)
+    }
+
+    fn just_released(&self, key: KeyCode) -> bool {
+        ButtonInput::just_released(self, key)
+    }
+
+    fn get_just_pressed(&self) -> impl Iterator<Item = KeyCode> {
+        ButtonInput::get_just_pressed(self).copied()
+    }
+
+    fn get_just_released(&self) -> impl Iterator<Item = KeyCode> {
+        ButtonInput::get_just_released(self).copied()
+    }
+}
+
+/// So systems can pass `&keyboard` straight from a `Res<ButtonInput<KeyCode>>`.
+impl KeyState for Res<'_, ButtonInput<KeyCode>> {
+    fn pressed(&self, key: KeyCode) -> bool {
+        ButtonInput::pressed(self, key)
+    }
+
+    fn just_pressed(&self, key: KeyCode) -> bool {
+        ButtonInput::just_pressed(self, key)
+    }
+
+    fn just_released(&self, key: KeyCode) -> bool {
+        ButtonInput::just_released(self, key)
+    }
+
+    fn get_just_pressed(&self) -> impl Iterator<Item = KeyCode> {
+        ButtonInput::get_just_pressed(self).copied()
+    }
+
+    fn get_just_released(&self) -> impl Iterator<Item = KeyCode> {
+        ButtonInput::get_just_released(self).copied()
+    }
+}
+
+impl KeyState for ResMut<'_, ButtonInput<KeyCode>> {
+    fn pressed(&self, key: KeyCode) -> bool {
+        ButtonInput::pressed(self, key)
+    }
+
+    fn just_pressed(&self, key: KeyCode) -> bool {
+        ButtonInput::just_pressed(self, key)
+    }
+
+    fn just_released(&self, key: KeyCode) -> bool {
+        ButtonInput::just_released(self, key)
+    }
+
+    fn get_just_pressed(&self) -> impl Iterator<Item = KeyCode> {
+        ButtonInput::get_just_pressed(self).copied()
+    }
+
+    fn get_just_released(&self) -> impl Iterator<Item = KeyCode> {
+        ButtonInput::get_just_released(self).copied()
+    }
+}
+
+/// The keys of the letters `A` to `Z`, in order.
+pub(crate) const LETTER_KEYS: [KeyCode; 26] = [
+    KeyCode::KeyA,
+    KeyCode::KeyB,
+    KeyCode::KeyC,
+    KeyCode::KeyD,
+    KeyCode::KeyE,
+    KeyCode::KeyF,
+    KeyCode::KeyG,
+    KeyCode::KeyH,
+    KeyCode::KeyI,
+    KeyCode::KeyJ,
+    KeyCode::KeyK,
+    KeyCode::KeyL,
+    KeyCode::KeyM,
+    KeyCode::KeyN,
+    KeyCode::KeyO,
+    KeyCode::KeyP,
+    KeyCode::KeyQ,
+    KeyCode::KeyR,
+    KeyCode::KeyS,
+    KeyCode::KeyT,
+    KeyCode::KeyU,
+    KeyCode::KeyV,
+    KeyCode::KeyW,
+    KeyCode::KeyX,
+    KeyCode::KeyY,
+    KeyCode::KeyZ,
+];
+
+/// The keys of the digits `0` to `9` above the letters, in order.
+pub(crate) const DIGIT_KEYS: [KeyCode; 10] = [
+    KeyCode::Digit0,
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+/// Keyboard state kept in plain sets, for feeding key events from outside
+/// Bevy's input systems.
+///
+/// Call [`KeySet::press`] and [`KeySet::release`] as events arrive and
+/// [`KeySet::clear`] at the end of each frame, as Bevy does for
+/// [`ButtonInput`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct KeySet {
+    pressed: HashSet<KeyCode>,
+    just_pressed: HashSet<KeyCode>,
+    just_released: HashSet<KeyCode>,
+}
+
+impl KeySet {
+    /// Records `key` going down. A key already held is not pressed again.
+    pub fn press(&mut self, key: KeyCode) {
+        if self.pressed.insert(key) {
+            self.just_pressed.insert(key);
+        }
+    }
+
+    /// Records `key` coming up.
+    pub fn release(&mut self, key: KeyCode) {
+        if self.pressed.remove(&key) {
+            self.just_released.insert(key);
+        }
+    }
+
+    /// Releases every held key, for when the keyboard is lost (a focus
+    /// change) and its releases will never arrive.
+    pub fn release_all(&mut self) {
+        self.just_released.extend(self.pressed.drain());
+    }
+
+    /// Ends the frame: forgets what was just pressed and released, keeping
+    /// what is held.
+    pub fn clear(&mut self) {
+        self.just_pressed.clear();
+        self.just_released.clear();
+    }
+
+    /// The keys held down.
+    pub fn get_pressed(&self) -> impl ExactSizeIterator<Item = &KeyCode> {
+        self.pressed.iter()
+    }
+}
+
+impl KeyState for KeySet {
+    fn pressed(&self, key: KeyCode) -> bool {
+        self.pressed.contains(&key)
+    }
+
+    fn just_pressed(&self, key: KeyCode) -> bool {
+        self.just_pressed.contains(&key)
+    }
+
+    fn just_released(&self, key: KeyCode) -> bool {
+        self.just_released.contains(&key)
+    }
+
+    fn get_just_pressed(&self) -> impl Iterator<Item = KeyCode> {
+        self.just_pressed.iter().copied()
+    }
+
+    fn get_just_released(&self) -> impl Iterator<Item = KeyCode> {
+        self.just_released.iter().copied()
+    }
+}
+
+/// Button state that shortcuts are matched against, modifiers included.
+pub(crate) trait ButtonSource {
+    fn modifiers_pressed(&self, modifiers: &Modifiers) -> bool;
+    fn button_pressed(&self, button: InputButton) -> bool;
+    fn button_just_pressed(&self, button: InputButton) -> bool;
+    fn button_just_released(&self, button: InputButton) -> bool;
+}
+
+/// The keyboard alone: mouse and gamepad bindings never match.
+impl<K: KeyState> ButtonSource for K {
+    fn modifiers_pressed(&self, modifiers: &Modifiers) -> bool {
+        modifiers.pressed(self) && modifiers.gamepad_free()
+    }
+
+    fn button_pressed(&self, button: InputButton) -> bool {
+        button.key().is_some_and(|key| KeyState::pressed(self, key))
+    }
+
+    fn button_just_pressed(&self, button: InputButton) -> bool {
+        button
+            .key()
+            .is_some_and(|key| KeyState::just_pressed(self, key))
+    }
+
+    fn button_just_released(&self, button: InputButton) -> bool {
+        button
+            .key()
+            .is_some_and(|key| KeyState::just_released(self, key))
+    }
+}
+
+#[cfg(feature = "mouse")]
+impl<K: KeyState> ButtonSource for (&K, &ButtonInput<MouseButton>) {
+    fn modifiers_pressed(&self, modifiers: &Modifiers) -> bool {
+        self.0.modifiers_pressed(modifiers)
+    }
+
+    fn button_pressed(&self, button: InputButton) -> bool {
+        match button {
+            InputButton::Key(key) => KeyState::pressed(self.0, key),
+            InputButton::Mouse(button) => self.1.pressed(button),
+            _ => false,
+        }
+    }
+
+    fn button_just_pressed(&self, button: InputButton) -> bool {
+        match button {
+            InputButton::Key(key) => KeyState::just_pressed(self.0, key),
+            InputButton::Mouse(button) => self.1.just_pressed(button),
+            _ => false,
+        }
+    }
+
+    fn button_just_released(&self, button: InputButton) -> bool {
+        match button {
+            InputButton::Key(key) => KeyState::just_released(self.0, key),
+            InputButton::Mouse(button) => self.1.just_released(button),
+            _ => false,
+        }
+    }
+}
+
+/// One gamepad alone: no keyboard modifier counts as pressed, and only its
+/// own buttons are read, held gamepad modifiers included.
+#[cfg(feature = "gamepad")]
+impl ButtonSource for Gamepad {
+    fn modifiers_pressed(&self, modifiers: &Modifiers) -> bool {
+        modifiers.pressed(&KeySet::default())
+            && modifiers.gamepad.iter().all(|&held| self.pressed(held))
+    }
+
+    fn button_pressed(&self, button: InputButton) -> bool {
+        button
+            .gamepad_button()
+            .is_some_and(|button| Gamepad::pressed(self, button))
+    }
+
+    fn button_just_pressed(&self, button: InputButton) -> bool {
+        button
+            .gamepad_button()
+            .is_some_and(|button| Gamepad::just_pressed(self, button))
+    }
+
+    fn button_just_released(&self, button: InputButton) -> bool {
+        button
+            .gamepad_button()
+            .is_some_and(|button| Gamepad::just_released(self, button))
+    }
+}
+
+/// How a [`Shortcut`] is written in keymap files: exactly one of `key`,
+/// `mouse`, `wheel` and `gamepad`, and its modifiers.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct ShortcutRepr {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key: Option<KeyCode>,
+    #[cfg(feature = "mouse")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mouse: Option<MouseButton>,
+    #[cfg(feature = "mouse")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wheel: Option<WheelDirection>,
+    #[cfg(feature = "gamepad")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gamepad: Option<GamepadButton>,
+    #[serde(default)]
+    modifiers: Modifiers,
+}
+
+/// How [`Modifiers`] are read from keymap files. Without the `gamepad`
+/// feature a `gamepad` field is rejected rather than ignored, as the schema
+/// does.
+#[derive(Deserialize)]
+pub(crate) struct ModifiersRepr {
+    #[serde(default)]
+    control: Option<ModifierType>,
+    #[serde(default)]
+    alt: Option<ModifierType>,
+    #[serde(default)]
+    shift: Option<ModifierType>,
+    #[serde(default)]
+    super_key: Option<ModifierType>,
+    #[cfg(feature = "gamepad")]
+    #[serde(default)]
+    gamepad: Vec<GamepadButton>,
+    #[cfg(not(feature = "gamepad"))]
+    #[serde(default)]
+    gamepad: Option<serde::de::IgnoredAny>,
+}
+
+impl TryFrom<ModifiersRepr> for Modifiers {
+    type Error = &'static str;
+
+    fn try_from(repr: ModifiersRepr) -> Result<Self, Self::Error> {
+        #[cfg(not(feature = "gamepad"))]
+        if repr.gamepad.is_some() {
+            return Err("gamepad modifiers need the `gamepad` feature");
+        }
+        Ok(Modifiers {
+            control: repr.control,
+            alt: repr.alt,
+            shift: repr.shift,
+            super_key: repr.super_key,
+            #[cfg(feature = "gamepad")]
+            gamepad: repr.gamepad,
+        })
+    }
+}
+
+#[cfg(not(any(feature = "mouse", feature = "gamepad")))]
+const MISSING_INPUT: &str = "a shortcut needs a `key`";
+#[cfg(all(feature = "mouse", not(feature = "gamepad")))]
+const MISSING_INPUT: &str = "a shortcut needs a `key`, a `mouse` button or a `wheel` direction";
+#[cfg(all(feature = "gamepad", not(feature = "mouse")))]
+const MISSING_INPUT: &str = "a shortcut needs a `key` or a `gamepad` button";
+#[cfg(all(feature = "mouse", feature = "gamepad"))]
+const MISSING_INPUT: &str =
+    "a shortcut needs a `key`, a `mouse` button, a `wheel` direction or a `gamepad` button";
+
+impl TryFrom<ShortcutRepr> for Shortcut {
+    type Error = &'static str;
+
+    fn try_from(repr: ShortcutRepr) -> Result<Self, Self::Error> {
+        #[cfg(any(feature = "mouse", feature = "gamepad"))]
+        let input = {
+            let mut inputs: Vec<InputButton> = repr.key.map(InputButton::Key).into_iter().collect();
+            #[cfg(feature = "mouse")]
+            inputs.extend(repr.mouse.map(InputButton::Mouse));
+            #[cfg(feature = "mouse")]
+            inputs.extend(repr.wheel.map(InputButton::Wheel));
+            #[cfg(feature = "gamepad")]
+            inputs.extend(repr.gamepad.map(InputButton::Gamepad));
+            match inputs[..] {
+                [input] => input,
+                [] => return Err(MISSING_INPUT),
+                _ => return Err("a shortcut binds one input field, not several"),
+            }
+        };
+        #[cfg(not(any(feature = "mouse", feature = "gamepad")))]
+        let input = InputButton::Key(repr.key.ok_or(MISSING_INPUT)?);
+
+        Ok(Shortcut {
+            input,
+            modifiers: repr.modifiers,
+        })
+    }
+}
+
+impl From<Shortcut> for ShortcutRepr {
+    fn from(shortcut: Shortcut) -> Self {
+        ShortcutRepr {
+            key: shortcut.input.key(),
+            #[cfg(feature = "mouse")]
+            mouse: shortcut.input.mouse_button(),
+            #[cfg(feature = "mouse")]
+            wheel: shortcut.input.wheel_direction(),
+            #[cfg(feature = "gamepad")]
+            gamepad: shortcut.input.gamepad_button(),
+            modifiers: shortcut.modifiers,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "mouse"))]
+mod tests {
+    use super::*;
+    use crate::{ModifierType, Shortcuts, keymap_schema};
+
+    #[test]
+    fn mouse_bindings_read_and_write_a_mouse_field() {
+        let json = r#"{"mouse":"Left","modifiers":{"control":"RequirePressed"}}"#;
+        let shortcut: Shortcut = serde_json::from_str(json).unwrap();
+
+        assert_eq!(shortcut.input, InputButton::Mouse(MouseButton::Left));
+        assert_eq!(
+            shortcut.modifiers.control,
+            Some(ModifierType::RequirePressed)
+        );
+        assert_eq!(shortcut.to_string(), "Ctrl + LMB");
+        assert_eq!(
+            serde_json::to_value(&shortcut).unwrap()["mouse"],
+            serde_json::json!("Left")
+        );
+
+        let both = r#"{"key":"KeyA","mouse":"Left"}"#;
+        let error = serde_json::from_str::<Shortcut>(both).unwrap_err();
+        assert!(error.to_string().contains("not several"), "{error}");
+
+        let schema = keymap_schema();
+        assert_eq!(
+            schema["$defs"]["Shortcut"]["properties"]["mouse"]["$ref"],
+            "#/$defs/MouseButton"
+        );
+    }
+
+    #[test]
+    fn mouse_buttons_match_with_keyboard_modifiers() {
+        let multi_select = Shortcuts::click(&[MouseButton::Left]).with_ctrl();
+        let mut keys = ButtonInput::<KeyCode>::default();
+        let mut mouse = ButtonInput::<MouseButton>::default();
+
+        mouse.press(MouseButton::Left);
+        assert!(!multi_select.pressed_with_mouse(&keys, &mouse), "no Ctrl");
+
+        keys.press(KeyCode::ControlLeft);
+        assert!(multi_select.pressed_with_mouse(&keys, &mouse));
+        assert!(
+            !multi_select.pressed(&keys),
+            "the keyboard alone cannot click"
+        );
+
+        mouse.clear();
+        assert!(
+            !multi_select.pressed_with_mouse(&keys, &mouse),
+            "single press"
+        );
+    }
+
+    #[test]
+    fn mouse_buttons_display_as_short_names() {
+        let names: Vec<String> = [
+            MouseButton::Left,
+            MouseButton::Right,
+            MouseButton::Middle,
+            MouseButton::Back,
+            MouseButton::Forward,
+            MouseButton::Other(7),
+        ]
+        .into_iter()
+        .map(|button| InputButton::from(button).to_string())
+        .collect();
+
+        assert_eq!(names, ["LMB", "RMB", "MMB", "Mouse4", "Mouse5", "Mouse7"]);
+    }
+}
@@ -0,0 +1,452 @@
+//! Named bindings, and user overrides layered over a default keymap.
+
+use crate::{KeyState, Shortcut, Shortcuts};
+#[cfg(feature = "gamepad")]
+use bevy::input::gamepad::Gamepad;
+#[cfg(feature = "mouse")]
+use bevy::input::mouse::MouseButton;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::collections::btree_map;
+
+/// A set of named bindings — every action of a game or tool with its
+/// [`Shortcuts`].
+///
+/// Serializes as a plain map from action name to binding, the same shape as
+/// the YAML configuration shown in the crate docs.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy::prelude::KeyCode;
+/// use bevy_keyboard_shortcuts::{Keymap, Shortcuts};
+///
+/// let keymap = Keymap::from_iter([
+///     ("jump", Shortcuts::single_press(&[KeyCode::Space])),
+///     ("quick_save", Shortcuts::single_press(&[KeyCode::KeyS]).with_ctrl()),
+/// ]);
+/// assert_eq!(keymap.get("quick_save").unwrap().to_string(), "Ctrl + S");
+/// ```
+#[derive(Reflect, Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "asset", derive(Asset))]
+#[serde(transparent)]
+pub struct Keymap {
+    actions: BTreeMap<String, Shortcuts>,
+}
+
+impl Keymap {
+    /// Creates an empty keymap.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The binding for `action`, if it has one.
+    pub fn get(&self, action: &str) -> Option<&Shortcuts> {
+        self.actions.get(action)
+    }
+
+    /// The binding for `action` for editing, if it has one.
+    pub fn get_mut(&mut self, action: &str) -> Option<&mut Shortcuts> {
+        self.actions.get_mut(action)
+    }
+
+    /// Binds `action`, returning its previous binding.
+    pub fn insert(&mut self, action: impl Into<String>, shortcuts: Shortcuts) -> Option<Shortcuts> {
+        self.actions.insert(action.into(), shortcuts)
+    }
+
+    /// Removes `action` entirely, returning its binding.
+    pub fn remove(&mut self, action: &str) -> Option<Shortcuts> {
+        self.actions.remove(action)
+    }
+
+    /// Iterates the actions and their bindings in name order.
+    pub fn iter(&self) -> btree_map::Iter<'_, String, Shortcuts> {
+        self.actions.iter()
+    }
+
+    /// The number of actions.
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    /// Returns `true` if the keymap has no actions.
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Checks whether `action` is activated, as [`Shortcuts::pressed`] does.
+    /// An action with no binding is never pressed.
+    pub fn pressed(&self, action: &str, keys: &impl KeyState) -> bool {
+        self.get(action)
+            .is_some_and(|shortcuts| shortcuts.pressed(keys))
+    }
+
+    /// [`Keymap::pressed`], for actions with mouse button alternatives.
+    #[cfg(feature = "mouse")]
+    pub fn pressed_with_mouse(
+        &self,
+        action: &str,
+        keys: &impl KeyState,
+        mouse: &ButtonInput<MouseButton>,
+    ) -> bool {
+        self.get(action)
+            .is_some_and(|shortcuts| shortcuts.pressed_with_mouse(keys, mouse))
+    }
+
+    /// [`Shortcuts::pressed_on_gamepad`] for `action`.
+    #[cfg(feature = "gamepad")]
+    pub fn pressed_on_gamepad(&self, action: &str, gamepad: &Gamepad) -> bool {
+        self.get(action)
+            .is_some_and(|shortcuts| shortcuts.pressed_on_gamepad(gamepad))
+    }
+
+    /// [`Shortcuts::pressed_on_any_gamepad`] for `action`.
+    #[cfg(feature = "gamepad")]
+    pub fn pressed_on_any_gamepad<'a>(
+        &self,
+        action: &str,
+        gamepads: impl IntoIterator<Item = &'a Gamepad>,
+    ) -> bool {
+        self.get(action)
+            .is_some_and(|shortcuts| shortcuts.pressed_on_any_gamepad(gamepads))
+    }
+
+    /// The effective keymap: this one, as the defaults, with `overrides`
+    /// applied on top.
+    pub fn with_overrides(&self, overrides: &KeymapOverrides) -> Keymap {
+        let mut effective = self.clone();
+        for (action, change) in &overrides.actions {
+            change.apply_to(&mut effective, action);
+        }
+        effective
+    }
+
+    /// The smallest overrides that turn this keymap into `edited`, so that
+    /// `self.with_overrides(&self.diff(&edited))` binds every action the way
+    /// `edited` does.
+    ///
+    /// An action missing from `edited` is [unbound](ActionOverride::Unbind)
+    /// rather than removed, so it still shows up — with no keys — in a
+    /// settings screen built from the effective keymap.
+    pub fn diff(&self, edited: &Keymap) -> KeymapOverrides {
+        let mut overrides = KeymapOverrides::default();
+
+        for (action, shortcuts) in &edited.actions {
+            let change = match self.actions.get(action) {
+                Some(base) if base == shortcuts => continue,
+                Some(base) => ActionOverride::between(base, shortcuts),
+                None => ActionOverride::Replace(shortcuts.clone()),
+            };
+            overrides.actions.insert(action.clone(), change);
+        }
+        for (action, base) in &self.actions {
+            if !edited.actions.contains_key(action) && base.iter().next().is_some() {
+                overrides
+                    .actions
+                    .insert(action.clone(), ActionOverride::Unbind);
+            }
+        }
+
+        overrides
+    }
+}
+
+impl<S: Into<String>> FromIterator<(S, Shortcuts)> for Keymap {
+    fn from_iter<I: IntoIterator<Item = (S, Shortcuts)>>(iter: I) -> Self {
+        Self {
+            actions: iter
+                .into_iter()
+                .map(|(action, shortcuts)| (action.into(), shortcuts))
+                .collect(),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Keymap {
+    type Item = (&'a String, &'a Shortcuts);
+    type IntoIter = btree_map::Iter<'a, String, Shortcuts>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.actions.iter()
+    }
+}
+
+/// A user's changes to a default [`Keymap`], stored sparsely so the config
+/// file holds only what they changed and picks up new defaults for the rest.
+///
+/// Serializes as a map from action name to [`ActionOverride`]:
+///
+/// ```yaml
+/// jump: Unbind
+/// quick_save:
+///   Replace:
+///     shortcuts:
+///       - key: "F5"
+/// move_left:
+///   Edit:
+///     add:
+///       - key: "KeyH"
+/// ```
+#[derive(Reflect, Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct KeymapOverrides {
+    actions: BTreeMap<String, ActionOverride>,
+}
+
+impl KeymapOverrides {
+    /// Creates an empty set of overrides.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The override for `action`, if it has one.
+    pub fn get(&self, action: &str) -> Option<&ActionOverride> {
+        self.actions.get(action)
+    }
+
+    /// Sets the override for `action`, returning the one it replaces.
+    pub fn insert(
+        &mut self,
+        action: impl Into<String>,
+        change: ActionOverride,
+    ) -> Option<ActionOverride> {
+        self.actions.insert(action.into(), change)
+    }
+
+    /// Drops the override for `action`, reverting it to the default.
+    pub fn remove(&mut self, action: &str) -> Option<ActionOverride> {
+        self.actions.remove(action)
+    }
+
+    /// Iterates the overridden actions in name order.
+    pub fn iter(&self) -> btree_map::Iter<'_, String, ActionOverride> {
+        self.actions.iter()
+    }
+
+    /// Returns `true` if nothing is overridden.
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+}
+
+/// How one action of a [`KeymapOverrides`] differs from the default.
+#[derive(Reflect, Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub enum ActionOverride {
+    /// Use this binding instead of the default, or bind an action the
+    /// defaults do not have.
+    Replace(Shortcuts),
+    /// Keep the action, with no keys.
+    Unbind,
+    /// Remove some of the default alternatives and add others, keeping the
+    /// default's trigger.
+    Edit {
+        /// Alternatives appended after the kept defaults
+        #[serde(default)]
+        add: Vec<Shortcut>,
+        /// Default alternatives to drop
+        #[serde(default)]
+        remove: Vec<Shortcut>,
+    },
+}
+
+impl ActionOverride {
+    fn apply_to(&self, keymap: &mut Keymap, action: &str) {
+        match self {
+            ActionOverride::Replace(shortcuts) => {
+                keymap.insert(action, shortcuts.clone());
+            }
+            ActionOverride::Unbind => {
+                if let Some(shortcuts) = keymap.get_mut(action) {
+                    shortcuts.shortcuts.clear();
+                }
+            }
+            ActionOverride::Edit { add, remove } => {
+                let shortcuts = keymap.actions.entry(action.to_string()).or_default();
+                shortcuts.shortcuts.retain(|s| !remove.contains(s));
+                for shortcut in add {
+                    if !shortcuts.shortcuts.contains(shortcut) {
+                        shortcuts.shortcuts.push(shortcut.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    /// The smallest override from `base` to a different `edited`.
+    fn between(base: &Shortcuts, edited: &Shortcuts) -> ActionOverride {
+        if base.repeats != edited.repeats || base.trigger != edited.trigger {
+            return ActionOverride::Replace(edited.clone());
+        }
+        if edited.shortcuts.is_empty() {
+            return ActionOverride::Unbind;
+        }
+
+        let remove: Vec<Shortcut> = base
+            .iter()
+            .filter(|s| !edited.shortcuts.contains(s))
+            .cloned()
+            .collect();
+        let add: Vec<Shortcut> = edited
+            .iter()
+            .filter(|s| !base.shortcuts.contains(s))
+            .cloned()
+            .collect();
+
+        // An edit keeps the defaults' order and appends; if the player
+        // reordered alternatives only a replace reproduces that.
+        let mut edited_by_edit = base.shortcuts.clone();
+        edited_by_edit.retain(|s| !remove.contains(s));
+        edited_by_edit.extend(add.iter().cloned());
+
+        if edited_by_edit == edited.shortcuts && add.len() + remove.len() <= edited.shortcuts.len()
+        {
+            ActionOverride::Edit { add, remove }
+        } else {
+            ActionOverride::Replace(edited.clone())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defaults() -> Keymap {
+        Keymap::from_iter([
+            ("jump", Shortcuts::single_press(&[KeyCode::Space])),
+            (
+                "move_left",
+                Shortcuts::repeating(&[KeyCode::KeyA, KeyCode::ArrowLeft]),
+            ),
+            (
+                "quick_save",
+                Shortcuts::single_press(&[KeyCode::KeyS]).with_ctrl(),
+            ),
+        ])
+    }
+
+    fn alternative(key: KeyCode) -> Shortcut {
+        Shortcuts::single_press(&[key])
+            .iter()
+            .next()
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn overrides_layer_over_the_defaults() {
+        let mut overrides = KeymapOverrides::new();
+        overrides.insert("jump", ActionOverride::Unbind);
+        overrides.insert(
+            "move_left",
+            ActionOverride::Edit {
+                add: vec![alternative(KeyCode::KeyH)],
+                remove: vec![alternative(KeyCode::ArrowLeft)],
+            },
+        );
+        overrides.insert(
+            "quick_save",
+            ActionOverride::Replace(Shortcuts::single_press(&[KeyCode::F5])),
+        );
+
+        let effective = defaults().with_overrides(&overrides);
+
+        assert_eq!(effective.get("jump").unwrap().to_string(), "");
+        assert_eq!(effective.get("move_left").unwrap().to_string(), "A, H");
+        assert!(
+            effective.get("move_left").unwrap().repeats,
+            "edits keep the trigger"
+        );
+        assert_eq!(effective.get("quick_save").unwrap().to_string(), "F5");
+    }
+
+    #[test]
+    fn an_unchanged_keymap_needs_no_overrides() {
+        assert!(defaults().diff(&defaults()).is_empty());
+    }
+
+    #[test]
+    fn diff_picks_the_smallest_override() {
+        let mut edited = defaults();
+        edited.insert(
+            "move_left",
+            Shortcuts::repeating(&[KeyCode::KeyA, KeyCode::ArrowLeft, KeyCode::KeyH]),
+        );
+        edited.insert("quick_save", Shortcuts::single_press(&[KeyCode::F5]));
+        edited.get_mut("jump").unwrap().shortcuts.clear();
+
+        let overrides = defaults().diff(&edited);
+
+        assert_eq!(
+            overrides.get("move_left"),
+            Some(&ActionOverride::Edit {
+                add: vec![alternative(KeyCode::KeyH)],
+                remove: vec![],
+            })
+        );
+        assert_eq!(
+            overrides.get("quick_save"),
+            Some(&ActionOverride::Replace(Shortcuts::single_press(&[
+                KeyCode::F5
+            ])))
+        );
+        assert_eq!(overrides.get("jump"), Some(&ActionOverride::Unbind));
+    }
+
+    #[test]
+    fn diff_round_trips_through_with_overrides() {
+        let mut edited = defaults();
+        edited.insert(
+            "move_left",
+            Shortcuts::repeating(&[KeyCode::ArrowLeft, KeyCode::KeyA]),
+        );
+        edited.insert("crouch", Shortcuts::repeating(&[KeyCode::KeyC]));
+        edited.insert("jump", Shortcuts::on_release(&[KeyCode::Space]));
+
+        let overrides = defaults().diff(&edited);
+
+        assert_eq!(defaults().with_overrides(&overrides), edited);
+        assert!(
+            matches!(overrides.get("move_left"), Some(ActionOverride::Replace(_))),
+            "a reorder needs a replace"
+        );
+    }
+
+    #[test]
+    fn an_action_dropped_from_the_edit_is_unbound() {
+        let mut edited = defaults();
+        edited.remove("jump");
+
+        let overrides = defaults().diff(&edited);
+
+        assert_eq!(overrides.get("jump"), Some(&ActionOverride::Unbind));
+        let effective = defaults().with_overrides(&overrides);
+        assert!(effective.get("jump").unwrap().iter().next().is_none());
+    }
+
+    #[test]
+    fn overrides_serialize_sparsely() {
+        let mut edited = defaults();
+        edited.insert("quick_save", Shortcuts::single_press(&[KeyCode::F5]));
+        let overrides = defaults().diff(&edited);
+
+        let json = serde_json::to_value(&overrides).unwrap();
+        assert_eq!(json.as_object().unwrap().len(), 1);
+
+        let loaded: KeymapOverrides = serde_json::from_str(
+            r#"{
+                "jump": "Unbind",
+                "move_left": { "Edit": { "add": [{ "key": "KeyH" }] } }
+            }"#,
+        )
+        .unwrap();
+        let effective = defaults().with_overrides(&loaded);
+        assert_eq!(
+            effective.get("move_left").unwrap().to_string(),
+            "A, Left, H"
+        );
+        assert_eq!(effective.get("jump").unwrap().to_string(), "");
+    }
+}
@@ -6,7 +6,30 @@
 //! # Features
 //!
 //! - Define keyboard shortcuts with optional modifiers
+//! - Mouse buttons as bindable inputs with the same modifiers, such as `Ctrl + LMB` (`mouse` feature)
+//! - Mouse wheel bindings that fire once per notch, such as `Ctrl + Wheel Up` (`mouse` feature)
+//! - Gamepad buttons with held gamepad modifiers such as `LB + A`, named in Xbox, PlayStation or Nintendo style (`gamepad` feature)
 //! - Support for repeating shortcuts (held keys) and single-press shortcuts
+//! - Virtual axes and 2D vectors from pairs of bindings, with optional smoothing
+//! - Release-triggered and hold-duration shortcuts (charge attacks, press-and-hold confirmation)
+//! - Fighting-game motion inputs with leniency windows and input buffering
+//! - Press buffering for `FixedUpdate` gameplay, with grace windows
+//! - Per-entity bindings and action state as components, for local multiplayer
+//! - "Press a key to bind" capture for settings screens, with conflict reports
+//! - Accelerators on UI nodes that fire an activation event, skipped in inactive, disabled or (`ui` feature) hidden subtrees
+//! - Menu mnemonics (`Alt` then an underlined letter), assigned automatically around `&File` markers
+//! - Named keymaps, with user overrides stored sparsely over the defaults
+//! - Switchable keymap profiles ("Default", "Left-handed") that inherit from each other
+//! - Keymap files as hot-reloadable Bevy assets in RON, JSON or TOML (`asset` feature)
+//! - Versioned keymap files, upgraded on load by renaming, splitting or re-defaulting actions
+//! - JSON Schema of the keymap format, for editor autocomplete of key names
+//! - Import of VS Code `keybindings.json` files, reporting what cannot be represented
+//! - Conversion to and from the `[input]` section of Godot's `project.godot`
+//! - Cheat sheets of the bindings as Markdown, HTML or plain text
+//! - Keyboard heatmaps (ASCII or SVG) of which actions sit on which keys
+//! - Matching against any keyboard state through the `KeyState` trait, not only Bevy's `ButtonInput`
+//! - Terminal consoles driven by crossterm key events (`crossterm` feature)
+//! - Per-window keyboard state for multi-window tools, released on focus loss (`window` feature)
 //! - Serialization/deserialization support via serde for easy configuration
 //! - Pretty-printing of shortcuts for UI display
 //!
@@ -96,8 +119,18 @@
 //!     - key: "KeyS"
 //!       modifiers:
 //!         control: RequirePressed
+//!
+//! # Example with a trigger - fires on release after a 0.8s hold
+//! charge_attack:
+//!   trigger:
+//!     ReleaseAfterHold:
+//!       hold_ms: 800
+//!   shortcuts:
+//!     - key: "Space"
 //! ```
 //!
+//! `trigger` is optional and defaults to `Press`; see [`Trigger`] for the modes.
+//!
 //! # Key Names Reference
 //!
 //! Key names in YAML/JSON configuration match Bevy's `KeyCode` enum variants exactly. Remove the `KeyCode::` prefix:
@@ -173,25 +206,96 @@
 //! assert_eq!(rebound.modifiers().control, Some(ModifierType::RequireNotPressed));
 //! ```
 
+#[cfg(feature = "gamepad")]
+use bevy::input::gamepad::GamepadButton;
 use bevy::input::keyboard::KeyCode;
+#[cfg(feature = "mouse")]
+use bevy::input::mouse::MouseButton;
 use bevy::prelude::*;
+use input::ButtonSource;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
 use std::sync::LazyLock;
-
-/// A single keyboard shortcut consisting of a key and optional modifiers.
+use std::time::Duration;
+
+mod action;
+#[cfg(feature = "asset")]
+mod asset;
+mod axis;
+mod buffer;
+mod cheatsheet;
+#[cfg(feature = "gamepad")]
+mod gamepad;
+mod godot;
+mod heatmap;
+mod input;
+mod keymap;
+mod migrate;
+mod mnemonic;
+mod motion;
+mod profile;
+mod rebind;
+mod schema;
+#[cfg(feature = "crossterm")]
+mod terminal;
+mod trigger;
+mod ui;
+mod vscode;
+#[cfg(feature = "mouse")]
+mod wheel;
+#[cfg(feature = "window")]
+mod window;
+
+pub use action::{ActionState, ActionStatePlugin, ShortcutBindings};
+#[cfg(feature = "asset")]
+pub use asset::{
+    KeymapAssetPlugin, KeymapFormat, KeymapLoadError, KeymapLoader, KeymapSource, parse_keymap,
+    parse_versioned_keymap,
+};
+pub use axis::{AxisSmoothing, AxisState, DPadState, OpposingInputs, VirtualAxis, VirtualDPad};
+pub use buffer::{ActionBuffer, ActionBufferPlugin};
+pub use cheatsheet::{CheatSheet, CheatSheetEntry};
+#[cfg(feature = "gamepad")]
+pub use gamepad::GamepadGlyphs;
+pub use godot::{
+    GodotExport, GodotImport, GodotIssue, GodotParseError, GodotProblem, export_godot_input,
+    import_godot_input,
+};
+pub use heatmap::{HeatmapBinding, KeyboardHeatmap};
+pub use input::{InputButton, KeySet, KeyState};
+pub use keymap::{ActionOverride, Keymap, KeymapOverrides};
+pub use migrate::{KeymapMigrations, Migration, MigrationError, MigrationFn, VersionedKeymap};
+pub use mnemonic::{MenuItem, MenuMnemonics, MnemonicCollision, MnemonicEvent, MnemonicNavigator};
+pub use motion::{KeyTransition, MotionBuffer, MotionMatch, MotionPattern, MotionStep};
+pub use profile::{
+    ActiveKeymap, KeymapProfile, KeymapProfiles, KeymapProfilesPlugin, ProfileError,
+};
+pub use rebind::{RebindCapture, RebindOutcome, RebindPlugin};
+pub use schema::keymap_schema;
+#[cfg(feature = "crossterm")]
+pub use terminal::TerminalKeys;
+pub use trigger::{ShortcutState, Trigger};
+pub use ui::{ShortcutActivated, ShortcutsInactive, UiShortcut, UiShortcutPlugin};
+pub use vscode::{VsCodeImport, VsCodeIssue, VsCodeProblem, import_vscode_keybindings};
+#[cfg(feature = "mouse")]
+pub use wheel::{MouseWheelNotches, MouseWheelPlugin, WheelDirection};
+#[cfg(feature = "window")]
+pub use window::{WindowKeys, WindowKeysPlugin, WindowScope};
+
+/// A single shortcut consisting of a key (or, with the `mouse` feature, a
+/// mouse button) and optional modifiers.
 ///
 /// Build and match shortcuts through [`Shortcuts`]; read them back through
 /// [`Shortcuts::iter`] when a UI needs the *structure* of a binding (each
 /// modifier and the key as separate parts — e.g. to draw keycaps) rather than
 /// the flat [`Display`] string.
-#[derive(Reflect, Debug, Clone, Deserialize, Serialize)]
+#[derive(Reflect, Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "input::ShortcutRepr", into = "input::ShortcutRepr")]
 pub struct Shortcut {
-    /// The main key that must be pressed
-    pub key: KeyCode,
+    /// The main key or button that must be pressed
+    pub input: InputButton,
     /// Optional modifier keys (Ctrl, Alt, Shift, Super)
-    #[serde(default)]
     pub modifiers: Modifiers,
 }
 
@@ -238,7 +342,12 @@ impl ModifierType {
 /// back off an existing binding ([`Shortcuts::modifiers`]) and re-applying them
 /// to another ([`Shortcuts::with_modifiers`]), which is what a rebind UI needs
 /// in order not to silently drop a `RequireNotPressed` the old binding carried.
-#[derive(Reflect, Debug, Clone, Default, Deserialize, Serialize)]
+///
+/// Non-exhaustive, since the `gamepad` feature adds a field: build one with
+/// [`Modifiers::new`] or from [`Modifiers::default`].
+#[derive(Reflect, Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(try_from = "input::ModifiersRepr")]
+#[non_exhaustive]
 pub struct Modifiers {
     /// Control/Command key requirement (None = ignore)
     #[serde(default)]
@@ -252,9 +361,31 @@ pub struct Modifiers {
     /// Super/Windows key requirement (None = ignore)
     #[serde(default)]
     pub super_key: Option<ModifierType>,
+    /// Gamepad buttons that must be held on the same gamepad, like `LB` for
+    /// a second layer of face-button actions
+    #[cfg(feature = "gamepad")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gamepad: Vec<GamepadButton>,
 }
 
 impl Modifiers {
+    /// Keyboard modifier requirements, holding no gamepad button.
+    pub fn new(
+        control: Option<ModifierType>,
+        alt: Option<ModifierType>,
+        shift: Option<ModifierType>,
+        super_key: Option<ModifierType>,
+    ) -> Self {
+        Self {
+            control,
+            alt,
+            shift,
+            super_key,
+            #[cfg(feature = "gamepad")]
+            gamepad: Vec::new(),
+        }
+    }
+
     /// Returns `true` if no modifiers are explicitly set (all are None).
     ///
     /// This checks if all modifiers are `None`, which is the default state meaning
@@ -264,18 +395,28 @@ impl Modifiers {
             && self.alt.is_none()
             && self.shift.is_none()
             && self.super_key.is_none()
+            && self.gamepad_free()
+    }
+
+    /// Returns `true` if no gamepad button has to be held, which the
+    /// keyboard alone can only satisfy then.
+    pub(crate) fn gamepad_free(&self) -> bool {
+        #[cfg(feature = "gamepad")]
+        return self.gamepad.is_empty();
+        #[cfg(not(feature = "gamepad"))]
+        true
     }
 
     /// Checks if the current keyboard state matches all modifier requirements.
     ///
     /// # Arguments
     ///
-    /// * `keys` - The current keyboard input state from Bevy
+    /// * `keys` - The current keyboard state, such as Bevy's `ButtonInput<KeyCode>`
     ///
     /// # Returns
     ///
     /// `true` if all modifier requirements are satisfied, `false` otherwise
-    pub fn pressed(&self, keys: &ButtonInput<KeyCode>) -> bool {
+    pub fn pressed(&self, keys: &impl KeyState) -> bool {
         let control_pressed =
             keys.pressed(KeyCode::ControlLeft) || keys.pressed(KeyCode::ControlRight);
         let alt_pressed = keys.pressed(KeyCode::AltLeft) || keys.pressed(KeyCode::AltRight);
@@ -288,6 +429,22 @@ impl Modifiers {
             && Self::matches_modifier(self.super_key.as_ref(), super_pressed)
     }
 
+    /// Returns `true` if some modifier state satisfies both `self` and `other`.
+    ///
+    /// Requirements clash only when one side requires a modifier pressed and
+    /// the other requires it not pressed; an ignored modifier is compatible
+    /// with anything.
+    pub fn compatible_with(&self, other: &Modifiers) -> bool {
+        let compatible = |a: &Option<ModifierType>, b: &Option<ModifierType>| match (a, b) {
+            (Some(a), Some(b)) => a == b,
+            _ => true,
+        };
+        compatible(&self.control, &other.control)
+            && compatible(&self.alt, &other.alt)
+            && compatible(&self.shift, &other.shift)
+            && compatible(&self.super_key, &other.super_key)
+    }
+
     /// Helper function to check if an optional modifier requirement matches the current state.
     fn matches_modifier(requirement: Option<&ModifierType>, pressed: bool) -> bool {
         match requirement {
@@ -320,11 +477,24 @@ impl Modifiers {
 
         parts
     }
+
+    /// Everything `Display` prints: the required modifiers, then any gamepad
+    /// buttons that must be held.
+    pub(crate) fn display_names(&self) -> Vec<String> {
+        let names = self.required_names().into_iter().map(String::from);
+        #[cfg(feature = "gamepad")]
+        let names = names.chain(
+            self.gamepad
+                .iter()
+                .map(|&held| InputButton::from(held).to_string()),
+        );
+        names.collect()
+    }
 }
 
 impl fmt::Display for Modifiers {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.required_names().join(" + "))
+        write!(f, "{}", self.display_names().join(" + "))
     }
 }
 
@@ -333,13 +503,25 @@ impl Shortcut {
     ///
     /// This converts Bevy's KeyCode debug format into a more user-friendly display string.
     /// For example, `KeyA` becomes `"A"`, `ArrowLeft` becomes `"Left"`, etc.
+    /// Mouse buttons read `"LMB"`, `"RMB"`, `"MMB"`, `"Mouse4"`, …
     pub fn key_str(&self) -> String {
-        let debug_str = format!("{:?}", self.key);
+        self.input.to_string()
+    }
+
+    /// The key this shortcut is bound to, or `None` for a mouse or gamepad
+    /// button. Replaces the `key` field of 0.6 and earlier.
+    pub fn key(&self) -> Option<KeyCode> {
+        self.input.key()
+    }
 
-        KEY_DISPLAY_MAP
-            .get(debug_str.as_str())
-            .map(|s| s.to_string())
-            .unwrap_or(debug_str)
+    /// Returns `true` if one key press can fire both `self` and `other`: the
+    /// same key, with modifier requirements that some modifier state
+    /// satisfies at once.
+    ///
+    /// Bare `S` conflicts with `Ctrl + S` (the bare binding ignores Ctrl), but
+    /// `S` with `RequireNotPressed` on Ctrl does not.
+    pub fn conflicts_with(&self, other: &Shortcut) -> bool {
+        self.input == other.input && self.modifiers.compatible_with(&other.modifiers)
     }
 
     /// Checks if the shortcut is currently being pressed (held down).
@@ -349,13 +531,13 @@ impl Shortcut {
     ///
     /// # Arguments
     ///
-    /// * `keys` - The current keyboard input state from Bevy
+    /// * `keys` - The current keyboard state, such as Bevy's `ButtonInput<KeyCode>`
     ///
     /// # Returns
     ///
     /// `true` if both the key and all required modifiers are currently pressed
-    pub fn pressed(&self, keys: &ButtonInput<KeyCode>) -> bool {
-        keys.pressed(self.key) && self.modifiers.pressed(keys)
+    pub fn pressed(&self, keys: &impl KeyState) -> bool {
+        self.pressed_in(keys)
     }
 
     /// Checks if the shortcut was just pressed this frame.
@@ -365,13 +547,74 @@ impl Shortcut {
     ///
     /// # Arguments
     ///
-    /// * `keys` - The current keyboard input state from Bevy
+    /// * `keys` - The current keyboard state, such as Bevy's `ButtonInput<KeyCode>`
     ///
     /// # Returns
     ///
     /// `true` if the key was just pressed this frame and all required modifiers are pressed
-    pub fn just_pressed(&self, keys: &ButtonInput<KeyCode>) -> bool {
-        keys.just_pressed(self.key) && self.modifiers.pressed(keys)
+    pub fn just_pressed(&self, keys: &impl KeyState) -> bool {
+        self.just_pressed_in(keys)
+    }
+
+    /// Checks if the shortcut was just released this frame.
+    ///
+    /// The key must come up while the modifiers still match: releasing `Ctrl`
+    /// before `S` ends a `Ctrl + S` chord without releasing it.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The current keyboard state, such as Bevy's `ButtonInput<KeyCode>`
+    ///
+    /// # Returns
+    ///
+    /// `true` if the key was just released this frame and all required modifiers are pressed
+    pub fn just_released(&self, keys: &impl KeyState) -> bool {
+        self.just_released_in(keys)
+    }
+
+    /// [`Shortcut::pressed`], for shortcuts that may be bound to a mouse
+    /// button; the keyboard-only checks never match those.
+    #[cfg(feature = "mouse")]
+    pub fn pressed_with_mouse(
+        &self,
+        keys: &impl KeyState,
+        mouse: &ButtonInput<MouseButton>,
+    ) -> bool {
+        self.pressed_in(&(keys, mouse))
+    }
+
+    /// [`Shortcut::just_pressed`], for shortcuts that may be bound to a mouse
+    /// button.
+    #[cfg(feature = "mouse")]
+    pub fn just_pressed_with_mouse(
+        &self,
+        keys: &impl KeyState,
+        mouse: &ButtonInput<MouseButton>,
+    ) -> bool {
+        self.just_pressed_in(&(keys, mouse))
+    }
+
+    /// [`Shortcut::just_released`], for shortcuts that may be bound to a
+    /// mouse button.
+    #[cfg(feature = "mouse")]
+    pub fn just_released_with_mouse(
+        &self,
+        keys: &impl KeyState,
+        mouse: &ButtonInput<MouseButton>,
+    ) -> bool {
+        self.just_released_in(&(keys, mouse))
+    }
+
+    pub(crate) fn pressed_in(&self, input: &impl ButtonSource) -> bool {
+        input.button_pressed(self.input) && input.modifiers_pressed(&self.modifiers)
+    }
+
+    pub(crate) fn just_pressed_in(&self, input: &impl ButtonSource) -> bool {
+        input.button_just_pressed(self.input) && input.modifiers_pressed(&self.modifiers)
+    }
+
+    pub(crate) fn just_released_in(&self, input: &impl ButtonSource) -> bool {
+        input.button_just_released(self.input) && input.modifiers_pressed(&self.modifiers)
     }
 }
 
@@ -382,8 +625,9 @@ impl fmt::Display for Shortcut {
         // requirements has `none() == false` but nothing to print, and testing
         // `none()` here rendered a bare `Ctrl: RequireNotPressed` binding on `1`
         // as `" + 1"`.
-        if !self.modifiers.required_names().is_empty() {
-            write!(f, "{} + ", self.modifiers)?
+        let modifiers = self.modifiers.to_string();
+        if !modifiers.is_empty() {
+            write!(f, "{modifiers} + ")?
         }
         write!(f, "{}", self.key_str())
     }
@@ -401,6 +645,10 @@ impl fmt::Display for Shortcut {
 ///
 /// - `Shortcuts::single_press(&[keys])` - Non-repeating shortcuts (single press)
 /// - `Shortcuts::repeating(&[keys])` - Repeating shortcuts (held)
+/// - `Shortcuts::on_release(&[keys])` - Shortcuts that fire when the key comes up
+/// - `Shortcuts::released_after_hold(&[keys], duration)` - Fire on release after a minimum hold
+/// - `Shortcuts::tap(&[keys], threshold)` / `Shortcuts::hold(&[keys], threshold)` - Tap vs hold on one key
+/// - `Shortcuts::multi_tap(&[keys], taps, window)` - Double-tap and other multi-tap sequences
 /// - `.with_ctrl()` - Add Ctrl modifier (chainable)
 /// - `.with_alt()` - Add Alt modifier (chainable)
 /// - `.with_shift()` - Add Shift modifier (chainable)
@@ -422,7 +670,7 @@ impl fmt::Display for Shortcut {
 /// // Multiple alternatives for movement
 /// let move_left = Shortcuts::repeating(&[KeyCode::KeyA, KeyCode::ArrowLeft]);
 /// ```
-#[derive(Reflect, Debug, Clone, Deserialize, Serialize, Default)]
+#[derive(Reflect, Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
 pub struct Shortcuts {
     /// List of alternative shortcuts that trigger the same action
     /// This field is public for serde deserialization but should not be accessed directly.
@@ -434,6 +682,11 @@ pub struct Shortcuts {
     #[serde(default)]
     #[doc(hidden)]
     pub repeats: bool,
+    /// What makes the binding fire. Defaults to [`Trigger::Press`], which
+    /// defers to `repeats`. Left out of saved files when it is the default.
+    #[serde(default, skip_serializing_if = "Trigger::is_press")]
+    #[doc(hidden)]
+    pub trigger: Trigger,
 }
 
 impl Shortcuts {
@@ -455,16 +708,7 @@ impl Shortcuts {
     /// let menu = Shortcuts::single_press(&[KeyCode::Escape, KeyCode::KeyM]);
     /// ```
     pub fn single_press(keys: &[KeyCode]) -> Self {
-        Self {
-            shortcuts: keys
-                .iter()
-                .map(|&key| Shortcut {
-                    key,
-                    modifiers: Modifiers::default(),
-                })
-                .collect(),
-            repeats: false,
-        }
+        Self::from_keys(keys, false, Trigger::Press)
     }
 
     /// Creates repeating shortcuts from a slice of keys (no modifiers).
@@ -485,15 +729,202 @@ impl Shortcuts {
     /// let move_left = Shortcuts::repeating(&[KeyCode::KeyA, KeyCode::ArrowLeft]);
     /// ```
     pub fn repeating(keys: &[KeyCode]) -> Self {
+        Self::from_keys(keys, true, Trigger::Press)
+    }
+
+    /// Creates shortcuts that fire when the key is released (no modifiers).
+    ///
+    /// Useful for actions that should wait until the player lets go, such as
+    /// confirming a drag or releasing a held throw.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bevy::prelude::KeyCode;
+    /// use bevy_keyboard_shortcuts::Shortcuts;
+    ///
+    /// let throw = Shortcuts::on_release(&[KeyCode::KeyG]);
+    /// ```
+    pub fn on_release(keys: &[KeyCode]) -> Self {
+        Self::from_keys(keys, false, Trigger::Release)
+    }
+
+    /// Creates shortcuts that fire on release, but only after the key has been
+    /// held for at least `hold` (no modifiers).
+    ///
+    /// Hold timing needs per-binding state, so evaluate these through a
+    /// [`ShortcutState`]; [`Shortcuts::pressed`] always returns `false` for them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bevy::prelude::KeyCode;
+    /// use bevy_keyboard_shortcuts::Shortcuts;
+    /// use std::time::Duration;
+    ///
+    /// let charge = Shortcuts::released_after_hold(&[KeyCode::Space], Duration::from_millis(800));
+    /// ```
+    pub fn released_after_hold(keys: &[KeyCode], hold: Duration) -> Self {
+        Self::from_keys(
+            keys,
+            false,
+            Trigger::ReleaseAfterHold {
+                hold_ms: trigger::duration_ms(hold),
+            },
+        )
+    }
+
+    /// Creates shortcuts that fire on release if the key was let go before
+    /// `threshold` (no modifiers).
+    ///
+    /// Pair with [`Shortcuts::hold`] on the same keys and threshold to give a
+    /// key separate tap and hold actions; each press fires exactly one of them.
+    /// Evaluate both through a [`ShortcutState`] each.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bevy::prelude::KeyCode;
+    /// use bevy_keyboard_shortcuts::Shortcuts;
+    /// use std::time::Duration;
+    ///
+    /// // Tap Tab to cycle targets, hold it to open the radial menu.
+    /// let threshold = Duration::from_millis(250);
+    /// let cycle_target = Shortcuts::tap(&[KeyCode::Tab], threshold);
+    /// let radial_menu = Shortcuts::hold(&[KeyCode::Tab], threshold);
+    /// ```
+    pub fn tap(keys: &[KeyCode], threshold: Duration) -> Self {
+        Self::from_keys(
+            keys,
+            false,
+            Trigger::Tap {
+                threshold_ms: trigger::duration_ms(threshold),
+            },
+        )
+    }
+
+    /// Creates shortcuts that fire once the key has been held for `threshold`,
+    /// while it is still down (no modifiers).
+    ///
+    /// See [`Shortcuts::tap`] for the matching tap half.
+    pub fn hold(keys: &[KeyCode], threshold: Duration) -> Self {
+        Self::from_keys(
+            keys,
+            false,
+            Trigger::Hold {
+                threshold_ms: trigger::duration_ms(threshold),
+            },
+        )
+    }
+
+    /// Creates shortcuts that fire on the `taps`-th successive press of the
+    /// same key, each within `window` of the last (no modifiers).
+    ///
+    /// Evaluate through a [`ShortcutState`]. Chain [`Shortcuts::unambiguous`]
+    /// to fire only once the window has run out with exactly `taps` presses.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bevy::prelude::KeyCode;
+    /// use bevy_keyboard_shortcuts::Shortcuts;
+    /// use std::time::Duration;
+    ///
+    /// let window = Duration::from_millis(250);
+    ///
+    /// // Double-tap a direction to dash
+    /// let dash = Shortcuts::multi_tap(&[KeyCode::KeyD, KeyCode::ArrowRight], 2, window);
+    ///
+    /// // `Esc Esc` clears the selection; a lone `Esc` only deselects the last
+    /// // item, and must not fire on the first half of `Esc Esc`.
+    /// let clear_selection = Shortcuts::multi_tap(&[KeyCode::Escape], 2, window);
+    /// let deselect_last = Shortcuts::multi_tap(&[KeyCode::Escape], 1, window).unambiguous();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug mode if `taps` is zero, which could never fire.
+    pub fn multi_tap(keys: &[KeyCode], taps: u8, window: Duration) -> Self {
+        debug_assert!(taps > 0, "multi_tap() needs at least one tap");
+        Self::from_keys(
+            keys,
+            false,
+            Trigger::MultiTap {
+                taps,
+                window_ms: trigger::duration_ms(window),
+                unambiguous: false,
+            },
+        )
+    }
+
+    /// Makes a multi-tap binding wait for its window to run out and fire only
+    /// if exactly the configured number of taps landed.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug mode if the binding is not a multi-tap.
+    pub fn unambiguous(mut self) -> Self {
+        debug_assert!(
+            matches!(self.trigger, Trigger::MultiTap { .. }),
+            "unambiguous() needs a multi-tap trigger"
+        );
+        if let Trigger::MultiTap { unambiguous, .. } = &mut self.trigger {
+            *unambiguous = true;
+        }
+        self
+    }
+
+    /// Creates shortcuts that fire when a mouse button is clicked (no
+    /// modifiers). Check them with [`Shortcuts::pressed_with_mouse`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bevy::prelude::MouseButton;
+    /// use bevy_keyboard_shortcuts::Shortcuts;
+    ///
+    /// let multi_select = Shortcuts::click(&[MouseButton::Left]).with_ctrl();
+    /// assert_eq!(multi_select.to_string(), "Ctrl + LMB");
+    /// ```
+    #[cfg(feature = "mouse")]
+    pub fn click(buttons: &[MouseButton]) -> Self {
+        Self::from_inputs(buttons.iter().map(|&b| b.into()), false, Trigger::Press)
+    }
+
+    /// Creates shortcuts that fire every frame a mouse button is held (no
+    /// modifiers), for drags such as orbiting a camera.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bevy::prelude::MouseButton;
+    /// use bevy_keyboard_shortcuts::Shortcuts;
+    ///
+    /// let orbit = Shortcuts::mouse_held(&[MouseButton::Middle]).with_alt();
+    /// ```
+    #[cfg(feature = "mouse")]
+    pub fn mouse_held(buttons: &[MouseButton]) -> Self {
+        Self::from_inputs(buttons.iter().map(|&b| b.into()), true, Trigger::Press)
+    }
+
+    fn from_keys(keys: &[KeyCode], repeats: bool, trigger: Trigger) -> Self {
+        Self::from_inputs(keys.iter().map(|&key| key.into()), repeats, trigger)
+    }
+
+    fn from_inputs(
+        inputs: impl Iterator<Item = InputButton>,
+        repeats: bool,
+        trigger: Trigger,
+    ) -> Self {
         Self {
-            shortcuts: keys
-                .iter()
-                .map(|&key| Shortcut {
-                    key,
+            shortcuts: inputs
+                .map(|input| Shortcut {
+                    input,
                     modifiers: Modifiers::default(),
                 })
                 .collect(),
-            repeats: true,
+            repeats,
+            trigger,
         }
     }
 
@@ -770,13 +1201,16 @@ impl Shortcuts {
 
     /// Checks if any of the shortcuts in this collection are activated.
     ///
-    /// The behavior depends on the `repeats` field:
+    /// The behavior depends on the trigger and the `repeats` field:
     /// - If `repeats` is `true`, checks if any shortcut is currently pressed (held)
     /// - If `repeats` is `false`, checks if any shortcut was just pressed this frame
+    /// - For [`Trigger::Release`], checks if any shortcut was just released this frame
+    /// - For timed triggers ([`Trigger::is_timed`]) always returns `false`; use a
+    ///   [`ShortcutState`] instead
     ///
     /// # Arguments
     ///
-    /// * `keys` - The current keyboard input state from Bevy
+    /// * `keys` - The current keyboard state, such as Bevy's `ButtonInput<KeyCode>`
     ///
     /// # Returns
     ///
@@ -796,11 +1230,43 @@ impl Shortcuts {
     ///     }
     /// }
     /// ```
-    pub fn pressed(&self, keys: &ButtonInput<KeyCode>) -> bool {
-        if self.repeats {
-            self.shortcuts.iter().any(|s| s.pressed(keys))
-        } else {
-            self.shortcuts.iter().any(|s| s.just_pressed(keys))
+    pub fn pressed(&self, keys: &impl KeyState) -> bool {
+        self.pressed_in(keys)
+    }
+
+    /// [`Shortcuts::pressed`], for bindings with mouse button alternatives;
+    /// the keyboard-only check never fires those.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use bevy_keyboard_shortcuts::Shortcuts;
+    ///
+    /// fn select(keyboard: Res<ButtonInput<KeyCode>>, mouse: Res<ButtonInput<MouseButton>>) {
+    ///     if Shortcuts::click(&[MouseButton::Left])
+    ///         .with_ctrl()
+    ///         .pressed_with_mouse(&keyboard, &mouse)
+    ///     {
+    ///         // Add the item under the cursor to the selection
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "mouse")]
+    pub fn pressed_with_mouse(
+        &self,
+        keys: &impl KeyState,
+        mouse: &ButtonInput<MouseButton>,
+    ) -> bool {
+        self.pressed_in(&(keys, mouse))
+    }
+
+    pub(crate) fn pressed_in(&self, input: &impl ButtonSource) -> bool {
+        match self.trigger {
+            Trigger::Press if self.repeats => self.shortcuts.iter().any(|s| s.pressed_in(input)),
+            Trigger::Press => self.shortcuts.iter().any(|s| s.just_pressed_in(input)),
+            Trigger::Release => self.shortcuts.iter().any(|s| s.just_released_in(input)),
+            _ => false,
         }
     }
 }
@@ -816,6 +1282,27 @@ impl Shortcuts {
     pub fn iter(&self) -> impl Iterator<Item = &Shortcut> {
         self.shortcuts.iter()
     }
+
+    /// Appends `other`'s alternatives, so one action can be bound to keys
+    /// and to mouse or gamepad buttons at once. `repeats` and the trigger
+    /// stay this binding's.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bevy::prelude::KeyCode;
+    /// use bevy_keyboard_shortcuts::Shortcuts;
+    ///
+    /// let undo = Shortcuts::single_press(&[KeyCode::KeyZ])
+    ///     .with_ctrl()
+    ///     .or(Shortcuts::single_press(&[KeyCode::Backspace]).with_alt());
+    /// assert_eq!(undo.to_string(), "Ctrl + Z, Alt + Backspace");
+    /// ```
+    #[must_use]
+    pub fn or(mut self, other: Shortcuts) -> Self {
+        self.shortcuts.extend(other.shortcuts);
+        self
+    }
 }
 
 impl fmt::Display for Shortcuts {
@@ -1069,6 +1556,23 @@ mod tests {
         assert!(unbound.modifiers().none());
     }
 
+    #[test]
+    fn a_shortcut_without_an_input_names_the_fields_it_accepts() {
+        let error = serde_json::from_str::<Shortcut>(r#"{"modifiers":{}}"#).unwrap_err();
+        assert!(
+            error.to_string().starts_with("a shortcut needs a `key`"),
+            "{error}"
+        );
+    }
+
+    #[cfg(not(feature = "gamepad"))]
+    #[test]
+    fn gamepad_modifiers_are_rejected_without_the_feature() {
+        let json = r#"{"key":"KeyA","modifiers":{"gamepad":["LeftTrigger"]}}"#;
+        let error = serde_json::from_str::<Shortcut>(json).unwrap_err();
+        assert!(error.to_string().contains("`gamepad` feature"), "{error}");
+    }
+
     #[test]
     fn with_modifiers_carries_a_requirement_across_a_rebind() {
         let old = Shortcuts::single_press(&[KeyCode::Digit1]).without_ctrl();
@@ -1080,8 +1584,8 @@ mod tests {
             Some(ModifierType::RequireNotPressed)
         );
         assert_eq!(
-            rebound.iter().map(|s| s.key).collect::<Vec<_>>(),
-            vec![KeyCode::Digit2]
+            rebound.iter().map(Shortcut::key).collect::<Vec<_>>(),
+            vec![Some(KeyCode::Digit2)]
         );
     }
 
@@ -1140,6 +1644,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn conflicts_need_the_same_key_and_compatible_modifiers() {
+        let first = |s: Shortcuts| s.iter().next().unwrap().clone();
+        let bare_s = first(Shortcuts::single_press(&[KeyCode::KeyS]));
+        let ctrl_s = first(Shortcuts::single_press(&[KeyCode::KeyS]).with_ctrl());
+        let s_without_ctrl = first(Shortcuts::single_press(&[KeyCode::KeyS]).without_ctrl());
+        let ctrl_d = first(Shortcuts::single_press(&[KeyCode::KeyD]).with_ctrl());
+
+        assert!(
+            bare_s.conflicts_with(&ctrl_s),
+            "bare S also fires on Ctrl + S"
+        );
+        assert!(!s_without_ctrl.conflicts_with(&ctrl_s));
+        assert!(!ctrl_s.conflicts_with(&ctrl_d));
+        assert!(ctrl_s.conflicts_with(&ctrl_s));
+    }
+
     #[test]
     fn test_shortcuts_display_single() {
         let shortcuts = Shortcuts::single_press(&[KeyCode::KeyA]);
@@ -1299,6 +1820,13 @@ mod tests {
             .with_ctrl();
     }
 
+    #[test]
+    #[should_panic(expected = "multi_tap() needs at least one tap")]
+    #[cfg(debug_assertions)]
+    fn test_shortcuts_zero_taps_panics() {
+        let _shortcuts = Shortcuts::multi_tap(&[KeyCode::KeyD], 0, Duration::from_millis(250));
+    }
+
     #[test]
     fn test_shortcuts_press_and_repeating() {
         let press_shortcuts = Shortcuts::single_press(&[KeyCode::KeyW]);
@@ -1308,6 +1836,70 @@ mod tests {
         assert!(repeating_shortcuts.repeats);
     }
 
+    #[test]
+    fn test_shortcuts_on_release() {
+        let shortcuts = Shortcuts::on_release(&[KeyCode::KeyE]);
+        let mut keys = ButtonInput::<KeyCode>::default();
+
+        // Pressing does not fire
+        keys.press(KeyCode::KeyE);
+        assert!(!shortcuts.pressed(&keys));
+
+        // Releasing does, for that frame only
+        keys.clear();
+        keys.release(KeyCode::KeyE);
+        assert!(shortcuts.pressed(&keys));
+        keys.clear();
+        assert!(!shortcuts.pressed(&keys));
+    }
+
+    #[test]
+    fn a_key_set_matches_like_button_input() {
+        let save = Shortcuts::single_press(&[KeyCode::KeyS]).with_ctrl();
+        let mut keys = KeySet::default();
+
+        keys.press(KeyCode::ControlRight);
+        keys.press(KeyCode::KeyS);
+        assert!(save.pressed(&keys));
+        keys.clear();
+        assert!(!save.pressed(&keys), "single press");
+        keys.press(KeyCode::KeyS);
+        assert!(!save.pressed(&keys), "already held");
+
+        keys.release_all();
+        assert!(keys.just_released(KeyCode::ControlRight));
+        assert_eq!(keys.get_pressed().len(), 0);
+        assert!(Shortcuts::on_release(&[KeyCode::KeyS]).pressed(&keys));
+    }
+
+    #[test]
+    fn test_shortcuts_on_release_needs_modifiers_held_at_release() {
+        let shortcuts = Shortcuts::on_release(&[KeyCode::KeyS]).with_ctrl();
+        let mut keys = ButtonInput::<KeyCode>::default();
+
+        keys.press(KeyCode::ControlLeft);
+        keys.press(KeyCode::KeyS);
+        keys.clear();
+
+        // Ctrl let go first - S coming up is no longer the chord
+        keys.release(KeyCode::ControlLeft);
+        keys.release(KeyCode::KeyS);
+        assert!(!shortcuts.pressed(&keys));
+    }
+
+    #[test]
+    fn test_shortcuts_timed_trigger_is_never_pressed_statelessly() {
+        let shortcuts =
+            Shortcuts::released_after_hold(&[KeyCode::Space], Duration::from_millis(500));
+        let mut keys = ButtonInput::<KeyCode>::default();
+
+        keys.press(KeyCode::Space);
+        assert!(!shortcuts.pressed(&keys));
+        keys.clear();
+        keys.release(KeyCode::Space);
+        assert!(!shortcuts.pressed(&keys));
+    }
+
     #[test]
     fn test_shortcuts_without_ctrl() {
         let shortcuts = Shortcuts::single_press(&[KeyCode::KeyS]).without_ctrl();
@@ -0,0 +1,475 @@
+//! Versioned keymap files, upgraded from older versions on load.
+
+use crate::Shortcuts;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// A keymap as saved to disk: the bindings, stamped with the version of the
+/// game's keymap layout they were written in.
+///
+/// ```json
+/// { "version": 3, "actions": { "jump": { "shortcuts": [{ "key": "Space" }] } } }
+/// ```
+///
+/// `actions` is kept untyped so files from older versions, whose layout may
+/// no longer deserialize, can be upgraded by [`KeymapMigrations`] first.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct VersionedKeymap {
+    /// The layout version `actions` is written in
+    pub version: u32,
+    /// Action name to binding, in that version's layout
+    #[serde(default)]
+    pub actions: Map<String, Value>,
+}
+
+/// One step of upgrading a keymap file from one version to the next.
+pub enum Migration {
+    /// The action was renamed; its binding moves to the new name.
+    RenameAction {
+        /// The old name
+        from: String,
+        /// The new name
+        to: String,
+    },
+    /// The action became several; each new action starts with its binding.
+    SplitAction {
+        /// The old name
+        from: String,
+        /// The new names
+        into: Vec<String>,
+    },
+    /// The action's default binding changed. Players still on the old default
+    /// get the new one; players who rebound it keep their choice.
+    ///
+    /// Bindings are compared in the current [`Shortcuts`] layout.
+    ChangeDefault {
+        /// The action
+        action: String,
+        /// The default it used to have
+        old: Shortcuts,
+        /// The default it has now
+        new: Shortcuts,
+    },
+    /// Any other change to the raw action map, such as a change to the serde
+    /// layout of [`Shortcuts`].
+    Custom(Box<MigrationFn>),
+}
+
+/// The raw action-map edit of a [`Migration::Custom`] step.
+pub type MigrationFn = dyn Fn(&mut Map<String, Value>) -> Result<(), String> + Send + Sync;
+
+impl Migration {
+    /// A [`Migration::Custom`] step.
+    pub fn custom(
+        step: impl Fn(&mut Map<String, Value>) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        Migration::Custom(Box::new(step))
+    }
+
+    fn apply(&self, actions: &mut Map<String, Value>) -> Result<(), String> {
+        match self {
+            Migration::RenameAction { from, to } => {
+                if let Some(binding) = actions.remove(from) {
+                    actions.insert(to.clone(), binding);
+                }
+            }
+            Migration::SplitAction { from, into } => {
+                if let Some(binding) = actions.remove(from) {
+                    for name in into {
+                        actions
+                            .entry(name.clone())
+                            .or_insert_with(|| binding.clone());
+                    }
+                }
+            }
+            Migration::ChangeDefault { action, old, new } => {
+                let Some(binding) = actions.get_mut(action) else {
+                    return Ok(());
+                };
+                if serde_json::from_value::<Shortcuts>(binding.clone()).is_ok_and(|b| b == *old) {
+                    *binding = serde_json::to_value(new).map_err(|e| e.to_string())?;
+                }
+            }
+            Migration::Custom(step) => step(actions)?,
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Migration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Migration::RenameAction { from, to } => f
+                .debug_struct("RenameAction")
+                .field("from", from)
+                .field("to", to)
+                .finish(),
+            Migration::SplitAction { from, into } => f
+                .debug_struct("SplitAction")
+                .field("from", from)
+                .field("into", into)
+                .finish(),
+            Migration::ChangeDefault { action, old, new } => f
+                .debug_struct("ChangeDefault")
+                .field("action", action)
+                .field("old", old)
+                .field("new", new)
+                .finish(),
+            Migration::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// Why a keymap file could not be upgraded and loaded.
+#[derive(Debug)]
+pub enum MigrationError {
+    /// The file was written by a newer version of the game
+    TooNew {
+        /// The file's version
+        version: u32,
+        /// The newest version these migrations know
+        current: u32,
+    },
+    /// The file is neither a versioned keymap nor a map of actions
+    NotAKeymap,
+    /// The file has a `version`, but it or `actions` is malformed
+    InvalidVersioned(serde_json::Error),
+    /// A migration step failed
+    Step {
+        /// The version the step upgrades to
+        version: u32,
+        /// What went wrong
+        message: String,
+    },
+    /// The upgraded actions do not deserialize in the current layout
+    Deserialize(serde_json::Error),
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MigrationError::TooNew { version, current } => write!(
+                f,
+                "keymap version {version} is newer than the latest known version {current}"
+            ),
+            MigrationError::NotAKeymap => f.write_str("not a keymap file"),
+            MigrationError::InvalidVersioned(error) => {
+                write!(f, "invalid versioned keymap: {error}")
+            }
+            MigrationError::Step { version, message } => {
+                write!(f, "upgrading keymap to version {version} failed: {message}")
+            }
+            MigrationError::Deserialize(error) => write!(f, "invalid upgraded keymap: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MigrationError::InvalidVersioned(error) | MigrationError::Deserialize(error) => {
+                Some(error)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A game's keymap layout history: the [`Migration`]s from each version to
+/// the next, run on load to bring old files up to date.
+///
+/// Version 1 is the first layout. Files saved before versioning — a plain
+/// map of actions, as [`Keymap`](crate::Keymap) serializes — load as version 1.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_keyboard_shortcuts::{Keymap, KeymapMigrations, Migration};
+///
+/// let mut migrations = KeymapMigrations::new();
+/// migrations
+///     .to_version(2, [Migration::RenameAction { from: "fire".into(), to: "attack".into() }])
+///     .to_version(
+///         3,
+///         [Migration::SplitAction {
+///             from: "use".into(),
+///             into: vec!["interact".into(), "pick_up".into()],
+///         }],
+///     );
+///
+/// let old_file = serde_json::json!({
+///     "fire": { "shortcuts": [{ "key": "KeyJ" }] },
+///     "use": { "shortcuts": [{ "key": "KeyE" }] },
+/// });
+/// let keymap: Keymap = migrations.load(old_file).unwrap();
+/// assert_eq!(keymap.get("attack").unwrap().to_string(), "J");
+/// assert_eq!(keymap.get("pick_up").unwrap().to_string(), "E");
+///
+/// // Saving stamps the latest version.
+/// assert_eq!(migrations.save(&keymap).unwrap().version, 3);
+/// ```
+#[derive(Debug, Default)]
+pub struct KeymapMigrations {
+    /// `versions[i]` upgrades version `i + 1` to `i + 2`
+    versions: Vec<Vec<Migration>>,
+}
+
+impl KeymapMigrations {
+    /// A history with only version 1.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the steps that upgrade the previous version to `version`.
+    ///
+    /// # Panics
+    ///
+    /// If `version` is not one past [`current_version`](Self::current_version):
+    /// versions are added in order, with no gaps.
+    pub fn to_version(
+        &mut self,
+        version: u32,
+        steps: impl IntoIterator<Item = Migration>,
+    ) -> &mut Self {
+        assert_eq!(
+            version,
+            self.current_version() + 1,
+            "keymap versions must be added in order"
+        );
+        self.versions.push(steps.into_iter().collect());
+        self
+    }
+
+    /// The latest version, which [`save`](Self::save) writes.
+    pub fn current_version(&self) -> u32 {
+        self.versions.len() as u32 + 1
+    }
+
+    /// Upgrades `file` to the current version.
+    pub fn migrate(&self, mut file: VersionedKeymap) -> Result<VersionedKeymap, MigrationError> {
+        let current = self.current_version();
+        if file.version > current {
+            return Err(MigrationError::TooNew {
+                version: file.version,
+                current,
+            });
+        }
+        for version in file.version.max(1) + 1..=current {
+            for step in &self.versions[version as usize - 2] {
+                step.apply(&mut file.actions)
+                    .map_err(|message| MigrationError::Step { version, message })?;
+            }
+        }
+        file.version = current;
+        Ok(file)
+    }
+
+    /// Reads a keymap file of any version — a [`VersionedKeymap`], or an
+    /// unversioned map of actions — upgrades it, and deserializes it as `T`.
+    ///
+    /// `T` is usually [`Keymap`](crate::Keymap), or [`KeymapOverrides`](crate::KeymapOverrides)
+    /// for sparse user files. `file` can come from any serde format:
+    /// deserialize the file into a [`serde_json::Value`] first.
+    ///
+    /// A file is versioned when its `version` is a number, or when it has both
+    /// `version` and `actions`; otherwise `version` is just an action's name.
+    /// A versioned file whose `version` is not a whole number that fits a
+    /// `u32`, or whose `actions` is not a map, is refused as
+    /// [`MigrationError::InvalidVersioned`] rather than read as version 1.
+    pub fn load<T: DeserializeOwned>(&self, file: Value) -> Result<T, MigrationError> {
+        let Value::Object(file) = file else {
+            return Err(MigrationError::NotAKeymap);
+        };
+        let versioned = file.get("version").is_some_and(Value::is_number)
+            || (file.contains_key("version") && file.contains_key("actions"));
+        let file = if versioned {
+            serde_json::from_value(Value::Object(file)).map_err(MigrationError::InvalidVersioned)?
+        } else {
+            VersionedKeymap {
+                version: 1,
+                actions: file,
+            }
+        };
+        let file = self.migrate(file)?;
+        serde_json::from_value(Value::Object(file.actions)).map_err(MigrationError::Deserialize)
+    }
+
+    /// Stamps `keymap` with the current version, ready to serialize.
+    pub fn save<T: Serialize>(&self, keymap: &T) -> Result<VersionedKeymap, serde_json::Error> {
+        let actions = match serde_json::to_value(keymap)? {
+            Value::Object(actions) => actions,
+            _ => Map::new(),
+        };
+        Ok(VersionedKeymap {
+            version: self.current_version(),
+            actions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ActionOverride, Keymap, KeymapOverrides, Trigger};
+    use bevy::input::keyboard::KeyCode;
+
+    /// The keymap history of an example game:
+    /// - v1 was written before triggers existed, with `hold_ms` on the binding
+    /// - v2 moved it into `trigger`, and renamed "fire" to "attack"
+    /// - v3 split "use" and moved quick save from F5 to Ctrl+S
+    fn game_history() -> KeymapMigrations {
+        let mut migrations = KeymapMigrations::new();
+        migrations
+            .to_version(
+                2,
+                [
+                    Migration::custom(|actions| {
+                        for binding in actions.values_mut() {
+                            // Override files also hold plain strings like "Unbind".
+                            let Some(binding) = binding.as_object_mut() else {
+                                continue;
+                            };
+                            if let Some(hold_ms) = binding.remove("hold_ms") {
+                                binding.insert(
+                                    "trigger".into(),
+                                    serde_json::json!({ "ReleaseAfterHold": { "hold_ms": hold_ms } }),
+                                );
+                            }
+                        }
+                        Ok(())
+                    }),
+                    Migration::RenameAction {
+                        from: "fire".into(),
+                        to: "attack".into(),
+                    },
+                ],
+            )
+            .to_version(
+                3,
+                [
+                    Migration::SplitAction {
+                        from: "use".into(),
+                        into: vec!["interact".into(), "pick_up".into()],
+                    },
+                    Migration::ChangeDefault {
+                        action: "quick_save".into(),
+                        old: Shortcuts::single_press(&[KeyCode::F5]),
+                        new: Shortcuts::single_press(&[KeyCode::KeyS]).with_ctrl(),
+                    },
+                ],
+            );
+        migrations
+    }
+
+    fn fixture(version: u32) -> Value {
+        let text = match version {
+            1 => include_str!("../fixtures/keymap/v1.json"),
+            2 => include_str!("../fixtures/keymap/v2.json"),
+            3 => include_str!("../fixtures/keymap/v3.json"),
+            _ => unreachable!(),
+        };
+        serde_json::from_str(text).unwrap()
+    }
+
+    fn binding(keymap: &Keymap, action: &str) -> String {
+        keymap.get(action).unwrap().to_string()
+    }
+
+    #[test]
+    fn every_previous_version_loads() {
+        let migrations = game_history();
+        for version in 1..=migrations.current_version() {
+            let keymap: Keymap = migrations
+                .load(fixture(version))
+                .unwrap_or_else(|e| panic!("v{version}: {e}"));
+            assert_eq!(binding(&keymap, "attack"), "J", "v{version}");
+            assert!(keymap.get("fire").is_none() && keymap.get("use").is_none());
+            assert_eq!(
+                keymap.get("charge").unwrap().trigger,
+                Trigger::ReleaseAfterHold { hold_ms: 800 },
+                "v{version}"
+            );
+        }
+    }
+
+    #[test]
+    fn migrations_keep_what_the_player_chose() {
+        let migrations = game_history();
+
+        let v1: Keymap = migrations.load(fixture(1)).unwrap();
+        assert_eq!(binding(&v1, "interact"), "E");
+        assert_eq!(binding(&v1, "pick_up"), "E", "split actions start alike");
+        assert_eq!(
+            binding(&v1, "quick_save"),
+            "Ctrl + S",
+            "was on the old default"
+        );
+
+        let v2: Keymap = migrations.load(fixture(2)).unwrap();
+        assert_eq!(binding(&v2, "pick_up"), "F", "rebound before the split");
+        assert_eq!(
+            binding(&v2, "quick_save"),
+            "F6",
+            "rebound away from the default"
+        );
+    }
+
+    #[test]
+    fn saving_writes_the_current_version() {
+        let migrations = game_history();
+        let keymap: Keymap = migrations.load(fixture(1)).unwrap();
+
+        let saved = migrations.save(&keymap).unwrap();
+        assert_eq!(saved.version, 3);
+        let json = serde_json::to_value(&saved).unwrap();
+        assert_eq!(migrations.load::<Keymap>(json).unwrap(), keymap);
+    }
+
+    #[test]
+    fn sparse_overrides_migrate_too() {
+        let migrations = game_history();
+        let overrides: KeymapOverrides = migrations
+            .load(serde_json::json!({ "fire": "Unbind" }))
+            .unwrap();
+        assert_eq!(overrides.get("attack"), Some(&ActionOverride::Unbind));
+    }
+
+    #[test]
+    fn newer_files_are_refused() {
+        let result =
+            game_history().load::<Keymap>(serde_json::json!({ "version": 4, "actions": {} }));
+        assert!(matches!(
+            result,
+            Err(MigrationError::TooNew {
+                version: 4,
+                current: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn a_malformed_version_is_refused_rather_than_read_as_version_one() {
+        for file in [
+            serde_json::json!({ "version": -1, "actions": {} }),
+            serde_json::json!({ "version": 2.5 }),
+            serde_json::json!({ "version": "2", "actions": {} }),
+            serde_json::json!({ "version": 2, "actions": [] }),
+        ] {
+            let result = game_history().load::<Keymap>(file.clone());
+            assert!(
+                matches!(result, Err(MigrationError::InvalidVersioned(_))),
+                "{file}: {result:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn an_action_named_version_is_not_a_version() {
+        let keymap: Keymap = game_history()
+            .load(serde_json::json!({ "version": { "shortcuts": [{ "key": "KeyV" }] } }))
+            .unwrap();
+        assert_eq!(binding(&keymap, "version"), "V");
+    }
+}
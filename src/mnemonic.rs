@@ -0,0 +1,595 @@
+//! Menu mnemonics: `Alt` to underline a letter per item, then the letter to
+//! pick it.
+
+use crate::input::{DIGIT_KEYS, LETTER_KEYS};
+use crate::{KeyState, Shortcuts};
+use bevy::input::keyboard::KeyCode;
+
+/// One entry of a menu tree, with its mnemonic.
+///
+/// The label marks an explicit mnemonic with `&` before the letter, as in
+/// `"&File"` or `"Save &As…"`; `&&` is a literal `&`. Items without a marker
+/// are given a letter by [`MenuMnemonics::new`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MenuItem<A> {
+    id: A,
+    /// The label without markers
+    text: String,
+    /// Index (in chars) into `text` of a letter marked with `&`
+    marked: Option<usize>,
+    /// Index (in chars) into `text` of the mnemonic, once assigned
+    underline: Option<usize>,
+    children: Vec<MenuItem<A>>,
+}
+
+impl<A> MenuItem<A> {
+    /// An item labelled `label`, `&` markers included.
+    pub fn new(id: A, label: &str) -> Self {
+        let mut text = String::new();
+        let mut marked = None;
+        let mut chars = label.chars();
+        while let Some(c) = chars.next() {
+            if c != '&' {
+                text.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('&') => text.push('&'),
+                Some(letter) => {
+                    if marked.is_none() && letter.is_ascii_alphanumeric() {
+                        marked = Some(text.chars().count());
+                    }
+                    text.push(letter);
+                }
+                None => {}
+            }
+        }
+        Self {
+            id,
+            text,
+            marked,
+            underline: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Gives the item a submenu (chainable).
+    pub fn with_children(mut self, children: impl IntoIterator<Item = MenuItem<A>>) -> Self {
+        self.children.extend(children);
+        self
+    }
+
+    /// What the item stands for.
+    pub fn id(&self) -> &A {
+        &self.id
+    }
+
+    /// The label to display, without `&` markers.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Which character of [`text`](Self::text) to underline, counted in
+    /// chars. `None` if the item has no mnemonic.
+    pub fn underline(&self) -> Option<usize> {
+        self.underline
+    }
+
+    /// The mnemonic letter, lowercase.
+    pub fn mnemonic(&self) -> Option<char> {
+        self.underline
+            .and_then(|i| self.text.chars().nth(i))
+            .map(|c| c.to_ascii_lowercase())
+    }
+
+    /// The submenu, empty for an item that is picked rather than opened.
+    pub fn children(&self) -> &[MenuItem<A>] {
+        &self.children
+    }
+
+    fn key(&self) -> Option<KeyCode> {
+        self.mnemonic().and_then(mnemonic_key)
+    }
+}
+
+/// Two sibling items marked with the same letter. The first keeps it; the
+/// second is given another, as if it had no marker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MnemonicCollision<A> {
+    /// The letter both were marked with
+    pub letter: char,
+    /// The item that kept it
+    pub kept: A,
+    /// The item that lost it
+    pub moved: A,
+}
+
+/// A menu tree with a unique mnemonic letter among the siblings of each
+/// level.
+///
+/// Marked letters are kept; the other items get, in order, the first free
+/// letter that starts a word of their label, then any free letter or digit
+/// in it. An item with none free has no mnemonic.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy_keyboard_shortcuts::{MenuItem, MenuMnemonics};
+///
+/// let menus = MenuMnemonics::new([
+///     MenuItem::new("file", "&File").with_children([
+///         MenuItem::new("save", "&Save"),
+///         MenuItem::new("save_as", "Save As…"),
+///     ]),
+///     MenuItem::new("format", "F&ormat"),
+/// ]);
+/// let file = &menus.items()[0];
+/// assert_eq!(file.mnemonic(), Some('f'));
+/// assert_eq!(menus.items()[1].mnemonic(), Some('o'));
+/// // "S" is taken by Save, so Save As takes the start of its next word.
+/// assert_eq!(file.children()[1].mnemonic(), Some('a'));
+/// assert!(menus.collisions().is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MenuMnemonics<A> {
+    items: Vec<MenuItem<A>>,
+    collisions: Vec<MnemonicCollision<A>>,
+}
+
+impl<A: Clone> MenuMnemonics<A> {
+    /// Assigns mnemonics to the menu bar `items` and all their submenus.
+    pub fn new(items: impl IntoIterator<Item = MenuItem<A>>) -> Self {
+        let mut menus = Self {
+            items: items.into_iter().collect(),
+            collisions: Vec::new(),
+        };
+        assign(&mut menus.items, &mut menus.collisions);
+        menus
+    }
+
+    /// The menu bar.
+    pub fn items(&self) -> &[MenuItem<A>] {
+        &self.items
+    }
+
+    /// Marked letters that clashed with a sibling's, to fix in the labels.
+    pub fn collisions(&self) -> &[MnemonicCollision<A>] {
+        &self.collisions
+    }
+
+    /// The bindings that open each menu bar item directly, such as
+    /// `Alt + F` for File.
+    pub fn accelerators(&self) -> impl Iterator<Item = (&A, Shortcuts)> {
+        self.items
+            .iter()
+            .filter_map(|item| Some((&item.id, menu_bar_binding(item.key()?))))
+    }
+
+    /// The items of the menu open at `path` (the menu bar for none).
+    /// `None` if the tree has changed and the path no longer resolves.
+    fn level(&self, path: &[usize]) -> Option<&[MenuItem<A>]> {
+        path.iter().try_fold(&self.items[..], |items, &i| {
+            items.get(i).map(|item| &item.children[..])
+        })
+    }
+}
+
+fn assign<A: Clone>(items: &mut [MenuItem<A>], collisions: &mut Vec<MnemonicCollision<A>>) {
+    // Marked letters first, each with the index of the item that kept it.
+    let mut taken: Vec<(char, usize)> = Vec::new();
+    for index in 0..items.len() {
+        let Some(at) = items[index].marked else {
+            continue;
+        };
+        let letter = char_at(&items[index].text, at);
+        match taken.iter().find(|&&(t, _)| t == letter) {
+            Some(&(_, first)) => collisions.push(MnemonicCollision {
+                letter,
+                kept: items[first].id.clone(),
+                moved: items[index].id.clone(),
+            }),
+            None => {
+                taken.push((letter, index));
+                items[index].underline = Some(at);
+            }
+        }
+    }
+
+    let mut taken: Vec<char> = taken.into_iter().map(|(letter, _)| letter).collect();
+    for item in items.iter_mut().filter(|item| item.underline.is_none()) {
+        let chars: Vec<char> = item.text.chars().collect();
+        let free = |i: usize| {
+            chars[i].is_ascii_alphanumeric() && !taken.contains(&chars[i].to_ascii_lowercase())
+        };
+        let word_start = |i: usize| i == 0 || !chars[i - 1].is_alphanumeric();
+        let chosen = (0..chars.len())
+            .find(|&i| word_start(i) && free(i))
+            .or_else(|| (0..chars.len()).find(|&i| free(i)));
+        if let Some(i) = chosen {
+            taken.push(chars[i].to_ascii_lowercase());
+            item.underline = Some(i);
+        }
+    }
+
+    for item in items {
+        assign(&mut item.children, collisions);
+    }
+}
+
+/// The lowercase character at `index` (in chars) of `text`.
+fn char_at(text: &str, index: usize) -> char {
+    text.chars()
+        .nth(index)
+        .map_or(' ', |c| c.to_ascii_lowercase())
+}
+
+/// The key that types a (lowercase) mnemonic.
+fn mnemonic_key(letter: char) -> Option<KeyCode> {
+    match letter {
+        'a'..='z' => Some(LETTER_KEYS[(letter as u8 - b'a') as usize]),
+        '0'..='9' => Some(DIGIT_KEYS[(letter as u8 - b'0') as usize]),
+        _ => None,
+    }
+}
+
+/// `Alt + letter`, opening a menu bar item while no menu is open.
+fn menu_bar_binding(key: KeyCode) -> Shortcuts {
+    Shortcuts::single_press(&[key])
+        .with_alt()
+        .without_ctrl()
+        .without_super()
+}
+
+/// A letter alone (or with `Alt` still held), picking an item of the menu
+/// bar once revealed, or of an open menu.
+fn menu_binding(key: KeyCode) -> Shortcuts {
+    Shortcuts::single_press(&[key])
+        .without_ctrl()
+        .without_super()
+}
+
+/// What a key press did to the menus.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MnemonicEvent<A> {
+    /// An item with a submenu was opened
+    Opened(A),
+    /// An item without a submenu was picked, and every menu closed
+    Activated(A),
+}
+
+/// Where mnemonic navigation is at.
+#[derive(Debug, Clone, Default, PartialEq)]
+enum Navigation {
+    /// Menus closed, underlines hidden unless `Alt` is held
+    #[default]
+    Idle,
+    /// `Alt` was tapped: underlines shown, letters pick menu bar items
+    Revealed,
+    /// A menu is open at this path of indices; letters pick its items
+    Open(Vec<usize>),
+}
+
+/// The desktop mnemonic flow over a [`MenuMnemonics`] tree.
+///
+/// - Holding `Alt` shows the underlines; `Alt + letter` opens that menu bar
+///   item.
+/// - Tapping `Alt` (pressing and releasing it alone) keeps them shown, and a
+///   letter alone then opens the item. Tapping it again hides them.
+/// - In an open menu, a letter opens or picks one of its items.
+/// - `Escape` closes the innermost menu, then hides the underlines.
+///
+/// Call [`update`](Self::update) once per frame and draw the menus from
+/// [`underlines_visible`](Self::underlines_visible) and
+/// [`open_menus`](Self::open_menus).
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_keyboard_shortcuts::{MenuItem, MenuMnemonics, MnemonicEvent, MnemonicNavigator};
+///
+/// #[derive(Resource)]
+/// struct Menus(MenuMnemonics<&'static str>);
+///
+/// fn navigate_menus(
+///     keyboard: Res<ButtonInput<KeyCode>>,
+///     menus: Res<Menus>,
+///     mut navigator: Local<MnemonicNavigator>,
+/// ) {
+///     if let Some(MnemonicEvent::Activated("save")) = navigator.update(&menus.0, &keyboard) {
+///         // Save
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MnemonicNavigator {
+    navigation: Navigation,
+    /// Whether `Alt` is held
+    alt_held: bool,
+    /// Whether the current `Alt` press has been alone so far
+    alt_alone: bool,
+}
+
+impl MnemonicNavigator {
+    /// Advances by one frame of keyboard input, returning what was opened or
+    /// picked.
+    pub fn update<A: Clone>(
+        &mut self,
+        menus: &MenuMnemonics<A>,
+        keys: &impl KeyState,
+    ) -> Option<MnemonicEvent<A>> {
+        let alt = [KeyCode::AltLeft, KeyCode::AltRight];
+        if alt.iter().any(|&key| keys.just_pressed(key)) && !self.alt_held {
+            self.alt_alone = true;
+        }
+        let was_held = self.alt_held;
+        self.alt_held = alt.iter().any(|&key| keys.pressed(key));
+        // Any other key pressed with `Alt` down makes its release part of a
+        // chord (`Alt + Tab`, `Alt + Q`) rather than a tap.
+        if self.alt_held && keys.get_just_pressed().any(|key| !alt.contains(&key)) {
+            self.alt_alone = false;
+        }
+
+        if keys.just_pressed(KeyCode::Escape) {
+            self.navigation = match std::mem::take(&mut self.navigation) {
+                Navigation::Open(mut path) => {
+                    path.pop();
+                    if path.is_empty() {
+                        Navigation::Revealed
+                    } else {
+                        Navigation::Open(path)
+                    }
+                }
+                Navigation::Idle | Navigation::Revealed => Navigation::Idle,
+            };
+            return None;
+        }
+
+        let mut path = match &self.navigation {
+            Navigation::Idle if self.alt_held => Some(Vec::new()),
+            Navigation::Idle => None,
+            Navigation::Revealed => Some(Vec::new()),
+            Navigation::Open(path) => Some(path.clone()),
+        };
+        // The tree changed under the open menu, such as a submenu of recent
+        // files emptying: close it rather than index past the end.
+        let level = path.as_deref().map(|path| menus.level(path));
+        if let Some(None) = level {
+            self.navigation = Navigation::Idle;
+            path = self.alt_held.then(Vec::new);
+        }
+        if let Some(mut path) = path {
+            let level = menus.level(&path).unwrap_or_default();
+            let menu_bar = matches!(self.navigation, Navigation::Idle);
+            let picked = level.iter().position(|item| {
+                item.key().is_some_and(|key| {
+                    let binding = if menu_bar {
+                        menu_bar_binding(key)
+                    } else {
+                        menu_binding(key)
+                    };
+                    binding.pressed(keys)
+                })
+            });
+            if let Some(index) = picked {
+                self.alt_alone = false;
+                path.push(index);
+                let item = &level[index];
+                return Some(if item.children.is_empty() {
+                    self.navigation = Navigation::Idle;
+                    MnemonicEvent::Activated(item.id.clone())
+                } else {
+                    self.navigation = Navigation::Open(path);
+                    MnemonicEvent::Opened(item.id.clone())
+                });
+            }
+        }
+
+        if was_held && !self.alt_held && self.alt_alone {
+            self.navigation = match self.navigation {
+                Navigation::Idle => Navigation::Revealed,
+                _ => Navigation::Idle,
+            };
+        }
+        None
+    }
+
+    /// Whether to draw the mnemonic underlines.
+    pub fn underlines_visible(&self) -> bool {
+        self.alt_held || self.navigation != Navigation::Idle
+    }
+
+    /// The open menus, from the menu bar item inwards.
+    pub fn open_menus<'a, A>(&self, menus: &'a MenuMnemonics<A>) -> Vec<&'a A> {
+        let Navigation::Open(path) = &self.navigation else {
+            return Vec::new();
+        };
+        let mut level = &menus.items[..];
+        let mut open = Vec::new();
+        for &index in path {
+            let Some(item) = level.get(index) else {
+                break;
+            };
+            open.push(&item.id);
+            level = &item.children;
+        }
+        open
+    }
+
+    /// Closes every menu and hides the underlines, as when the menus lose
+    /// focus.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::KeySet;
+
+    fn menus() -> MenuMnemonics<&'static str> {
+        MenuMnemonics::new([
+            MenuItem::new("file", "&File").with_children([
+                MenuItem::new("new", "&New"),
+                MenuItem::new("export", "E&xport")
+                    .with_children([MenuItem::new("png", "PNG"), MenuItem::new("svg", "SVG")]),
+            ]),
+            MenuItem::new("edit", "&Edit"),
+            MenuItem::new("view", "Vi&ew"),
+            MenuItem::new("tools", "Tools && Add-ons"),
+        ])
+    }
+
+    fn tap(keys: &mut KeySet, key: KeyCode, navigator: &mut MnemonicNavigator) {
+        let menus = menus();
+        keys.press(key);
+        navigator.update(&menus, keys);
+        keys.clear();
+        keys.release(key);
+        navigator.update(&menus, keys);
+        keys.clear();
+    }
+
+    #[test]
+    fn letters_are_unique_among_siblings() {
+        let menus = menus();
+        let [file, edit, view, tools] = menus.items() else {
+            panic!("four menus");
+        };
+        assert_eq!(file.mnemonic(), Some('f'));
+        assert_eq!(edit.mnemonic(), Some('e'));
+        assert_eq!(view.mnemonic(), Some('v'), "its marked E collides");
+        assert_eq!(view.underline(), Some(0));
+        assert_eq!(tools.text(), "Tools & Add-ons");
+        assert_eq!(tools.mnemonic(), Some('t'));
+        assert_eq!(
+            menus.collisions(),
+            [MnemonicCollision {
+                letter: 'e',
+                kept: "edit",
+                moved: "view",
+            }]
+        );
+
+        let export = &file.children()[1];
+        assert_eq!(export.underline(), Some(1));
+        let [png, svg] = export.children() else {
+            panic!("two formats");
+        };
+        assert_eq!((png.mnemonic(), svg.mnemonic()), (Some('p'), Some('s')));
+
+        let accelerators: Vec<String> = menus
+            .accelerators()
+            .map(|(_, binding)| binding.to_string())
+            .collect();
+        assert_eq!(accelerators[0], "Alt + F");
+    }
+
+    #[test]
+    fn alt_tap_then_letters_walk_the_tree() {
+        let menus = menus();
+        let mut navigator = MnemonicNavigator::default();
+        let mut keys = KeySet::default();
+
+        tap(&mut keys, KeyCode::AltLeft, &mut navigator);
+        assert!(navigator.underlines_visible());
+
+        keys.press(KeyCode::KeyF);
+        assert_eq!(
+            navigator.update(&menus, &keys),
+            Some(MnemonicEvent::Opened("file"))
+        );
+        keys.clear();
+        keys.release(KeyCode::KeyF);
+        keys.press(KeyCode::KeyX);
+        assert_eq!(
+            navigator.update(&menus, &keys),
+            Some(MnemonicEvent::Opened("export"))
+        );
+        assert_eq!(navigator.open_menus(&menus), [&"file", &"export"]);
+
+        tap(&mut keys, KeyCode::Escape, &mut navigator);
+        assert_eq!(navigator.open_menus(&menus), [&"file"]);
+        keys.release(KeyCode::KeyX);
+        keys.press(KeyCode::KeyN);
+        assert_eq!(
+            navigator.update(&menus, &keys),
+            Some(MnemonicEvent::Activated("new"))
+        );
+        assert!(!navigator.underlines_visible());
+    }
+
+    #[test]
+    fn an_open_menu_removed_from_the_tree_closes() {
+        let mut navigator = MnemonicNavigator::default();
+        let mut keys = KeySet::default();
+
+        tap(&mut keys, KeyCode::AltLeft, &mut navigator);
+        keys.press(KeyCode::KeyF);
+        navigator.update(&menus(), &keys);
+        keys.clear();
+        keys.release(KeyCode::KeyF);
+        keys.press(KeyCode::KeyX);
+        navigator.update(&menus(), &keys);
+        keys.clear();
+        keys.release(KeyCode::KeyX);
+
+        let shrunk = MenuMnemonics::new([
+            MenuItem::new("file", "&File").with_children([MenuItem::new("new", "&New")])
+        ]);
+        keys.press(KeyCode::KeyP);
+        assert_eq!(navigator.update(&shrunk, &keys), None);
+        assert!(navigator.open_menus(&shrunk).is_empty());
+        assert!(!navigator.underlines_visible());
+    }
+
+    #[test]
+    fn alt_chords_open_directly_and_do_not_count_as_taps() {
+        let menus = menus();
+        let mut navigator = MnemonicNavigator::default();
+        let mut keys = KeySet::default();
+
+        keys.press(KeyCode::AltLeft);
+        navigator.update(&menus, &keys);
+        keys.clear();
+        keys.press(KeyCode::KeyE);
+        assert_eq!(
+            navigator.update(&menus, &keys),
+            Some(MnemonicEvent::Activated("edit"))
+        );
+        keys.clear();
+        keys.release_all();
+        navigator.update(&menus, &keys);
+        assert!(!navigator.underlines_visible(), "Alt + E was no tap");
+
+        // A letter alone does nothing until Alt is tapped.
+        keys.clear();
+        keys.press(KeyCode::KeyF);
+        assert_eq!(navigator.update(&menus, &keys), None);
+        keys.clear();
+        keys.release_all();
+
+        keys.press(KeyCode::AltLeft);
+        navigator.update(&menus, &keys);
+        keys.clear();
+        keys.press(KeyCode::Tab);
+        navigator.update(&menus, &keys);
+        keys.clear();
+        keys.release_all();
+        navigator.update(&menus, &keys);
+        assert!(!navigator.underlines_visible(), "Alt + Tab was no tap");
+
+        // Nor is Alt with a letter that no menu uses.
+        keys.clear();
+        keys.press(KeyCode::AltLeft);
+        navigator.update(&menus, &keys);
+        keys.clear();
+        keys.press(KeyCode::KeyQ);
+        assert_eq!(navigator.update(&menus, &keys), None);
+        keys.clear();
+        keys.release_all();
+        navigator.update(&menus, &keys);
+        assert!(!navigator.underlines_visible(), "Alt + Q was no tap");
+    }
+}
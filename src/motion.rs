@@ -0,0 +1,513 @@
+//! Fighting-game style motion inputs recognised from a timestamped key history.
+
+use crate::{KeyState, Shortcuts};
+use bevy::input::keyboard::KeyCode;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// One key going down or up, as recorded by a [`MotionBuffer`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyTransition {
+    /// The key that changed
+    pub key: KeyCode,
+    /// `true` for a press, `false` for a release
+    pub pressed: bool,
+    /// The [`MotionBuffer::frame`] it was recorded on
+    pub frame: u64,
+    /// Clock time it was recorded at
+    pub time: Duration,
+    /// Every key held once this transition applied
+    pub held: Vec<KeyCode>,
+}
+
+/// One position of a motion, such as `Down-Forward` or `Forward + Punch`.
+///
+/// Each entry of `inputs` is a binding that must be held at once — reusing the
+/// action bindings means `Down` can be `S` or `ArrowDown` without listing each
+/// combination. The step is reached by the press that completes the set.
+/// Extra keys held on top are tolerated, the leniency players expect from
+/// sliding a stick through a diagonal.
+#[derive(Reflect, Debug, Clone, Deserialize, Serialize)]
+pub struct MotionStep {
+    /// Bindings that must all be held
+    pub inputs: Vec<Shortcuts>,
+    /// Longest gap after the previous step, in milliseconds. Ignored on the
+    /// first step.
+    pub window_ms: u32,
+}
+
+/// A sequence of [`MotionStep`]s, such as a quarter-circle forward punch.
+///
+/// Patterns are facing-agnostic: a game whose characters turn around keeps one
+/// pattern per facing and asks about the one that matches.
+#[derive(Reflect, Debug, Clone, Deserialize, Serialize)]
+pub struct MotionPattern {
+    /// Steps in the order they must be reached
+    pub steps: Vec<MotionStep>,
+    /// Presses of unrelated keys allowed between two steps. Releases never
+    /// count: letting go of `Down` on the way to `Forward` is part of the motion.
+    #[serde(default)]
+    pub max_noise: usize,
+    /// How many frames after completing the motion still report it, so a
+    /// special entered during hitstun or a few frames early is not lost.
+    #[serde(default)]
+    pub buffer_frames: u64,
+}
+
+/// A completed motion, as found by [`MotionBuffer::find`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MotionMatch {
+    /// Frame of the press that reached the first step
+    pub started_frame: u64,
+    /// Frame of the press that reached the last step
+    pub completed_frame: u64,
+    /// Clock time of the press that reached the last step
+    pub completed_at: Duration,
+}
+
+/// A timestamped history of key transitions that [`MotionPattern`]s are
+/// matched against.
+///
+/// Call [`update`](Self::update) once per frame, then [`find`](Self::find)
+/// each pattern; [`consume`](Self::consume) a match once it has been acted on
+/// so it does not fire again on the next frame of its buffer window. Keep one
+/// buffer per player.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_keyboard_shortcuts::{MotionBuffer, MotionPattern, MotionStep, Shortcuts};
+/// use std::time::Duration;
+///
+/// let down = Shortcuts::repeating(&[KeyCode::KeyS, KeyCode::ArrowDown]);
+/// let forward = Shortcuts::repeating(&[KeyCode::KeyD, KeyCode::ArrowRight]);
+/// let punch = Shortcuts::repeating(&[KeyCode::KeyJ]);
+///
+/// // Down, Down-Forward, Forward + Punch
+/// let fireball = MotionPattern {
+///     steps: vec![
+///         MotionStep { inputs: vec![down.clone()], window_ms: 0 },
+///         MotionStep { inputs: vec![down, forward.clone()], window_ms: 150 },
+///         MotionStep { inputs: vec![forward, punch], window_ms: 150 },
+///     ],
+///     max_noise: 1,
+///     buffer_frames: 4,
+/// };
+///
+/// let mut buffer = MotionBuffer::new(Duration::from_secs(1));
+/// let keyboard = ButtonInput::<KeyCode>::default();
+/// let time = Time::<()>::default();
+///
+/// // Every frame:
+/// buffer.update(&keyboard, &time);
+/// if let Some(motion) = buffer.find(&fireball) {
+///     buffer.consume(&motion);
+///     // Throw the fireball, timed from `motion.completed_frame`.
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MotionBuffer {
+    history: VecDeque<KeyTransition>,
+    held: Vec<KeyCode>,
+    frame: u64,
+    now: Duration,
+    retention: Duration,
+}
+
+impl MotionBuffer {
+    /// Creates an empty buffer that forgets transitions older than `retention`.
+    pub fn new(retention: Duration) -> Self {
+        Self {
+            history: VecDeque::new(),
+            held: Vec::new(),
+            frame: 0,
+            now: Duration::ZERO,
+            retention,
+        }
+    }
+
+    /// Records this frame's transitions and advances the frame counter.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The current keyboard state, such as Bevy's `ButtonInput<KeyCode>`
+    /// * `time` - The clock transitions are stamped with
+    pub fn update<T: Default>(&mut self, keys: &impl KeyState, time: &Time<T>) {
+        self.frame += 1;
+        self.now = time.elapsed();
+
+        // A key both pressed and released this frame was tapped if it is up
+        // now, and released then pressed again if it is down: record its two
+        // transitions in that order, so it ends where the keyboard has it.
+        let released: Vec<KeyCode> = keys.get_just_released().collect();
+        let (released_first, released_last): (Vec<KeyCode>, Vec<KeyCode>) =
+            released.into_iter().partition(|&key| keys.pressed(key));
+        for key in released_first {
+            self.release(key);
+        }
+        for key in keys.get_just_pressed() {
+            if !self.held.contains(&key) {
+                self.held.push(key);
+            }
+            self.record(key, true);
+        }
+        for key in released_last {
+            self.release(key);
+        }
+
+        while self
+            .history
+            .front()
+            .is_some_and(|t| self.now.saturating_sub(t.time) > self.retention)
+        {
+            self.history.pop_front();
+        }
+    }
+
+    fn release(&mut self, key: KeyCode) {
+        self.held.retain(|&held| held != key);
+        self.record(key, false);
+    }
+
+    fn record(&mut self, key: KeyCode, pressed: bool) {
+        self.history.push_back(KeyTransition {
+            key,
+            pressed,
+            frame: self.frame,
+            time: self.now,
+            held: self.held.clone(),
+        });
+    }
+
+    /// The number of [`update`](Self::update)s so far.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// The recorded transitions, oldest first.
+    pub fn history(&self) -> impl Iterator<Item = &KeyTransition> {
+        self.history.iter()
+    }
+
+    /// Forgets every recorded transition.
+    pub fn clear(&mut self) {
+        self.history.clear();
+    }
+
+    /// Finds the most recent completion of `pattern` still inside its
+    /// `buffer_frames`, if any.
+    pub fn find(&self, pattern: &MotionPattern) -> Option<MotionMatch> {
+        let first = pattern.steps.first()?;
+        let oldest_frame = self.frame.saturating_sub(pattern.buffer_frames);
+
+        (0..self.history.len())
+            .filter(|&start| self.reaches(start, first))
+            .filter_map(|start| {
+                let end = self.complete(pattern, 1, start)?;
+                Some(MotionMatch {
+                    started_frame: self.history[start].frame,
+                    completed_frame: self.history[end].frame,
+                    completed_at: self.history[end].time,
+                })
+            })
+            .filter(|m| m.completed_frame >= oldest_frame)
+            .max_by_key(|m| (m.completed_frame, m.started_frame))
+    }
+
+    /// Drops every transition up to and including the frame `motion`
+    /// completed on, so the same presses cannot complete it — or any other
+    /// pattern — again.
+    pub fn consume(&mut self, motion: &MotionMatch) {
+        while self
+            .history
+            .front()
+            .is_some_and(|t| t.frame <= motion.completed_frame)
+        {
+            self.history.pop_front();
+        }
+    }
+
+    /// Matches steps `step..` after the transition at `previous`, returning
+    /// where the last step was reached.
+    fn complete(&self, pattern: &MotionPattern, step: usize, previous: usize) -> Option<usize> {
+        let Some(motion_step) = pattern.steps.get(step) else {
+            return Some(previous);
+        };
+        let window = Duration::from_millis(motion_step.window_ms.into());
+        let since = self.history[previous].time;
+        let mut noise = 0;
+
+        for index in previous + 1..self.history.len() {
+            let transition = &self.history[index];
+            if transition.time.saturating_sub(since) > window {
+                break;
+            }
+            if self.reaches(index, motion_step) {
+                if let Some(end) = self.complete(pattern, step + 1, index) {
+                    return Some(end);
+                }
+            } else if transition.pressed {
+                noise += 1;
+                if noise > pattern.max_noise {
+                    break;
+                }
+            }
+        }
+        None
+    }
+
+    /// Whether the transition at `index` is a press that completes `step`.
+    fn reaches(&self, index: usize, step: &MotionStep) -> bool {
+        let transition = &self.history[index];
+        if !transition.pressed {
+            return false;
+        }
+        let held = HeldKeys(&transition.held);
+
+        let mut completes = false;
+        let all_held = step.inputs.iter().all(|input| {
+            input.iter().any(|shortcut| {
+                let matched = shortcut.pressed(&held);
+                completes |= matched && shortcut.input == transition.key.into();
+                matched
+            })
+        });
+        all_held && completes
+    }
+}
+
+/// The keys held at a recorded transition, matched against without copying
+/// them into a keyboard state.
+struct HeldKeys<'a>(&'a [KeyCode]);
+
+impl KeyState for HeldKeys<'_> {
+    fn pressed(&self, key: KeyCode) -> bool {
+        self.0.contains(&key)
+    }
+
+    fn just_pressed(&self, _key: KeyCode) -> bool {
+        false
+    }
+
+    fn just_released(&self, _key: KeyCode) -> bool {
+        false
+    }
+
+    fn get_just_pressed(&self) -> impl Iterator<Item = KeyCode> {
+        std::iter::empty()
+    }
+
+    fn get_just_released(&self) -> impl Iterator<Item = KeyCode> {
+        std::iter::empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::KeySet;
+
+    const DOWN: KeyCode = KeyCode::KeyS;
+    const FORWARD: KeyCode = KeyCode::KeyD;
+    const PUNCH: KeyCode = KeyCode::KeyJ;
+
+    fn fireball(window_ms: u32, max_noise: usize) -> MotionPattern {
+        let bind = |keys: &[KeyCode]| -> Vec<Shortcuts> {
+            keys.iter()
+                .map(|&key| Shortcuts::repeating(&[key]))
+                .collect()
+        };
+        MotionPattern {
+            steps: vec![
+                MotionStep {
+                    inputs: bind(&[DOWN]),
+                    window_ms: 0,
+                },
+                MotionStep {
+                    inputs: bind(&[DOWN, FORWARD]),
+                    window_ms,
+                },
+                MotionStep {
+                    inputs: bind(&[FORWARD, PUNCH]),
+                    window_ms,
+                },
+            ],
+            max_noise,
+            buffer_frames: 2,
+        }
+    }
+
+    struct Driver {
+        buffer: MotionBuffer,
+        keys: ButtonInput<KeyCode>,
+        time: Time,
+    }
+
+    impl Driver {
+        fn new() -> Self {
+            Self {
+                buffer: MotionBuffer::new(Duration::from_secs(2)),
+                keys: ButtonInput::default(),
+                time: Time::default(),
+            }
+        }
+
+        /// Runs one 16ms frame with `press`ed and `release`d keys.
+        fn frame(&mut self, press: &[KeyCode], release: &[KeyCode]) {
+            self.keys.clear();
+            self.time.advance_by(Duration::from_millis(16));
+            for &key in release {
+                self.keys.release(key);
+            }
+            for &key in press {
+                self.keys.press(key);
+            }
+            self.buffer.update(&self.keys, &self.time);
+        }
+
+        fn idle(&mut self, frames: usize) {
+            for _ in 0..frames {
+                self.frame(&[], &[]);
+            }
+        }
+    }
+
+    #[test]
+    fn recognises_a_clean_quarter_circle() {
+        let mut d = Driver::new();
+        d.frame(&[DOWN], &[]);
+        d.frame(&[FORWARD], &[]);
+        d.frame(&[], &[DOWN]);
+        assert_eq!(d.buffer.find(&fireball(100, 0)), None, "no punch yet");
+        d.frame(&[PUNCH], &[]);
+
+        let motion = d.buffer.find(&fireball(100, 0)).unwrap();
+        assert_eq!(motion.started_frame, 1);
+        assert_eq!(motion.completed_frame, 4);
+    }
+
+    #[test]
+    fn a_step_outside_its_window_breaks_the_motion() {
+        let mut d = Driver::new();
+        d.frame(&[DOWN], &[]);
+        d.frame(&[FORWARD], &[]);
+        d.frame(&[], &[DOWN]);
+        d.idle(10); // 160ms, past a 100ms window
+        d.frame(&[PUNCH], &[]);
+
+        assert_eq!(d.buffer.find(&fireball(100, 0)), None);
+        assert!(d.buffer.find(&fireball(250, 0)).is_some());
+    }
+
+    #[test]
+    fn intermediate_noise_is_tolerated_up_to_the_limit() {
+        let mut d = Driver::new();
+        d.frame(&[DOWN], &[]);
+        d.frame(&[KeyCode::KeyK], &[]); // a stray kick
+        d.frame(&[FORWARD], &[]);
+        d.frame(&[], &[DOWN, KeyCode::KeyK]);
+        d.frame(&[PUNCH], &[]);
+
+        assert_eq!(d.buffer.find(&fireball(100, 0)), None);
+        assert!(d.buffer.find(&fireball(100, 1)).is_some());
+    }
+
+    #[test]
+    fn a_completed_motion_is_buffered_then_expires() {
+        let mut d = Driver::new();
+        d.frame(&[DOWN], &[]);
+        d.frame(&[FORWARD], &[]);
+        d.frame(&[PUNCH], &[DOWN]);
+        let pattern = fireball(100, 0);
+
+        d.idle(2);
+        assert_eq!(d.buffer.find(&pattern).unwrap().completed_frame, 3);
+        d.idle(1);
+        assert_eq!(d.buffer.find(&pattern), None, "outside buffer_frames");
+    }
+
+    #[test]
+    fn consuming_a_motion_stops_it_reporting_again() {
+        let mut d = Driver::new();
+        d.frame(&[DOWN], &[]);
+        d.frame(&[FORWARD], &[]);
+        d.frame(&[PUNCH], &[]);
+        let pattern = fireball(100, 0);
+
+        let motion = d.buffer.find(&pattern).unwrap();
+        d.buffer.consume(&motion);
+        d.idle(1);
+        assert_eq!(d.buffer.find(&pattern), None);
+    }
+
+    #[test]
+    fn steps_match_any_alternative_of_their_bindings() {
+        let down = Shortcuts::repeating(&[DOWN, KeyCode::ArrowDown]);
+        let forward = Shortcuts::repeating(&[FORWARD, KeyCode::ArrowRight]);
+        let pattern = MotionPattern {
+            steps: vec![
+                MotionStep {
+                    inputs: vec![down.clone()],
+                    window_ms: 0,
+                },
+                MotionStep {
+                    inputs: vec![down, forward],
+                    window_ms: 100,
+                },
+            ],
+            max_noise: 0,
+            buffer_frames: 0,
+        };
+
+        let mut d = Driver::new();
+        d.frame(&[KeyCode::ArrowDown], &[]);
+        d.frame(&[KeyCode::ArrowRight], &[]);
+        assert!(d.buffer.find(&pattern).is_some());
+    }
+
+    #[test]
+    fn a_key_tapped_within_one_frame_ends_up_released() {
+        let mut d = Driver::new();
+        d.keys.clear();
+        d.time.advance_by(Duration::from_millis(16));
+        d.keys.press(DOWN);
+        d.keys.release(DOWN);
+        d.buffer.update(&d.keys, &d.time);
+
+        let last = d.buffer.history().last().unwrap();
+        assert_eq!((last.key, last.pressed), (DOWN, false));
+        assert!(last.held.is_empty());
+
+        d.frame(&[FORWARD], &[]);
+        d.frame(&[PUNCH], &[]);
+        assert_eq!(d.buffer.find(&fireball(100, 0)), None, "Down is not held");
+    }
+
+    #[test]
+    fn a_key_released_and_pressed_again_within_one_frame_stays_held() {
+        let mut buffer = MotionBuffer::new(Duration::from_secs(2));
+        let mut keys = KeySet::default();
+        let time = Time::<()>::default();
+        keys.press(DOWN);
+        buffer.update(&keys, &time);
+        keys.clear();
+        keys.release(DOWN);
+        keys.press(DOWN);
+        buffer.update(&keys, &time);
+
+        let last = buffer.history().last().unwrap();
+        assert_eq!((last.key, last.pressed), (DOWN, true));
+        assert_eq!(last.held, [DOWN]);
+    }
+
+    #[test]
+    fn old_transitions_are_forgotten() {
+        let mut d = Driver::new();
+        d.frame(&[DOWN], &[]);
+        d.idle(200);
+        assert_eq!(d.buffer.history().count(), 0);
+    }
+}
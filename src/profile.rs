@@ -0,0 +1,384 @@
+//! Named keymap presets, with inheritance and runtime switching.
+
+use crate::{Keymap, KeymapOverrides};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// One preset of a [`KeymapProfiles`] registry, such as "Left-handed".
+///
+/// A profile either stands alone, listing every binding in `keymap`, or is
+/// `based_on` another profile and lists only how it differs: `keymap` entries
+/// replace the inherited binding of the same action, then `overrides` apply
+/// on top (see [`KeymapOverrides`]).
+#[derive(Reflect, Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct KeymapProfile {
+    /// Name of the profile this one inherits from
+    #[serde(default)]
+    pub based_on: Option<String>,
+    /// Bindings this profile sets outright
+    #[serde(default)]
+    pub keymap: Keymap,
+    /// Changes to the bindings it inherits
+    #[serde(default)]
+    pub overrides: KeymapOverrides,
+}
+
+impl KeymapProfile {
+    /// A profile that stands alone with `keymap`.
+    pub fn new(keymap: Keymap) -> Self {
+        Self {
+            keymap,
+            ..Self::default()
+        }
+    }
+
+    /// A profile inheriting from `base` with `overrides` on top.
+    pub fn based_on(base: impl Into<String>, overrides: KeymapOverrides) -> Self {
+        Self {
+            based_on: Some(base.into()),
+            overrides,
+            ..Self::default()
+        }
+    }
+}
+
+/// Why a profile could not be resolved or selected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProfileError {
+    /// No profile has this name
+    UnknownProfile(String),
+    /// A profile is based on one that does not exist
+    MissingBase {
+        /// The inheriting profile
+        profile: String,
+        /// The name it is based on
+        based_on: String,
+    },
+    /// Profiles inherit from each other in a loop; the names in order
+    Cycle(Vec<String>),
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProfileError::UnknownProfile(name) => write!(f, "no keymap profile named {name:?}"),
+            ProfileError::MissingBase { profile, based_on } => write!(
+                f,
+                "keymap profile {profile:?} is based on {based_on:?}, which does not exist"
+            ),
+            ProfileError::Cycle(names) => {
+                write!(
+                    f,
+                    "keymap profiles inherit in a loop: {}",
+                    names.join(" -> ")
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProfileError {}
+
+/// A registry of complete keymaps keyed by name — shipped presets such as
+/// "Default" or "Classic RTS" alongside the player's own.
+///
+/// [`select`](Self::select) picks the active profile; with
+/// [`KeymapProfilesPlugin`] its resolved bindings land in [`ActiveKeymap`] at
+/// the start of the next frame, all at once, so no system ever runs with half
+/// the old bindings and half the new. Editing a profile through the resource
+/// re-resolves it the same way.
+///
+/// The whole registry — every profile and the active choice — serializes
+/// with serde, for a settings file.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_keyboard_shortcuts::{
+///     ActionOverride, Keymap, KeymapOverrides, KeymapProfile, KeymapProfiles, Shortcuts,
+/// };
+///
+/// let mut profiles = KeymapProfiles::default();
+/// profiles.insert(
+///     "Default",
+///     KeymapProfile::new(Keymap::from_iter([
+///         ("move_left", Shortcuts::repeating(&[KeyCode::KeyA])),
+///         ("move_right", Shortcuts::repeating(&[KeyCode::KeyD])),
+///     ])),
+/// );
+///
+/// let mut left_handed = KeymapOverrides::new();
+/// left_handed.insert("move_left", ActionOverride::Replace(Shortcuts::repeating(&[KeyCode::KeyJ])));
+/// left_handed.insert("move_right", ActionOverride::Replace(Shortcuts::repeating(&[KeyCode::KeyL])));
+/// profiles.insert("Left-handed", KeymapProfile::based_on("Default", left_handed));
+///
+/// profiles.select("Left-handed").unwrap();
+/// assert_eq!(
+///     profiles.resolve("Left-handed").unwrap().get("move_left").unwrap().to_string(),
+///     "J"
+/// );
+/// ```
+#[derive(Resource, Reflect, Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct KeymapProfiles {
+    profiles: BTreeMap<String, KeymapProfile>,
+    #[serde(default)]
+    active: Option<String>,
+}
+
+impl KeymapProfiles {
+    /// Adds or replaces the profile called `name`, returning the old one.
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        profile: KeymapProfile,
+    ) -> Option<KeymapProfile> {
+        self.profiles.insert(name.into(), profile)
+    }
+
+    /// The profile called `name`, if there is one.
+    pub fn get(&self, name: &str) -> Option<&KeymapProfile> {
+        self.profiles.get(name)
+    }
+
+    /// The profile called `name` for editing, if there is one.
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut KeymapProfile> {
+        self.profiles.get_mut(name)
+    }
+
+    /// Removes the profile called `name`. Profiles based on it stop
+    /// resolving until they are re-based or it is added back.
+    pub fn remove(&mut self, name: &str) -> Option<KeymapProfile> {
+        self.profiles.remove(name)
+    }
+
+    /// The profile names, in order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.profiles.keys().map(String::as_str)
+    }
+
+    /// The name of the selected profile, if one has been selected.
+    pub fn active(&self) -> Option<&str> {
+        self.active.as_deref()
+    }
+
+    /// Makes `name` the active profile.
+    ///
+    /// Fails, leaving the selection unchanged, if the profile does not exist
+    /// or does not resolve.
+    pub fn select(&mut self, name: &str) -> Result<(), ProfileError> {
+        self.resolve(name)?;
+        self.active = Some(name.to_string());
+        Ok(())
+    }
+
+    /// The complete bindings of the profile called `name`, following its
+    /// `based_on` chain.
+    pub fn resolve(&self, name: &str) -> Result<Keymap, ProfileError> {
+        let mut chain: Vec<&str> = Vec::new();
+        let mut next = name;
+        loop {
+            if chain.contains(&next) {
+                chain.push(next);
+                return Err(ProfileError::Cycle(
+                    chain.into_iter().map(String::from).collect(),
+                ));
+            }
+            let profile = self.profiles.get(next).ok_or_else(|| match chain.last() {
+                None => ProfileError::UnknownProfile(next.to_string()),
+                Some(profile) => ProfileError::MissingBase {
+                    profile: profile.to_string(),
+                    based_on: next.to_string(),
+                },
+            })?;
+            chain.push(next);
+            match &profile.based_on {
+                Some(base) => next = base,
+                None => break,
+            }
+        }
+
+        // Apply from the root down.
+        let mut keymap = Keymap::new();
+        for name in chain.into_iter().rev() {
+            let profile = &self.profiles[name];
+            for (action, shortcuts) in &profile.keymap {
+                keymap.insert(action.clone(), shortcuts.clone());
+            }
+            keymap = keymap.with_overrides(&profile.overrides);
+        }
+        Ok(keymap)
+    }
+}
+
+/// The bindings currently in effect: the active profile of
+/// [`KeymapProfiles`], resolved.
+///
+/// Gameplay reads bindings from here rather than from the registry.
+#[derive(Resource, Deref, DerefMut, Debug, Clone, Default)]
+pub struct ActiveKeymap(pub Keymap);
+
+/// Adds [`KeymapProfiles`] and [`ActiveKeymap`], and re-resolves the active
+/// profile into `ActiveKeymap` in `First` whenever the registry changes.
+///
+/// With this plugin, load keymap files with
+/// `KeymapSource::for_profile` so they feed a profile instead of writing
+/// `ActiveKeymap` themselves.
+#[derive(Default)]
+pub struct KeymapProfilesPlugin;
+
+impl Plugin for KeymapProfilesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<KeymapProfiles>()
+            .init_resource::<ActiveKeymap>()
+            .add_systems(
+                First,
+                apply_active_profile.run_if(resource_changed::<KeymapProfiles>),
+            );
+    }
+}
+
+fn apply_active_profile(profiles: Res<KeymapProfiles>, mut active: ResMut<ActiveKeymap>) {
+    let Some(name) = profiles.active() else {
+        return;
+    };
+    // `select` validated the profile, but it may have been edited or
+    // removed since; keep the last good bindings rather than unbind
+    // everything mid-game.
+    if let Ok(keymap) = profiles.resolve(name) {
+        active.0 = keymap;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ActionOverride, Shortcuts};
+
+    fn registry() -> KeymapProfiles {
+        let mut profiles = KeymapProfiles::default();
+        profiles.insert(
+            "Default",
+            KeymapProfile::new(Keymap::from_iter([
+                ("move_left", Shortcuts::repeating(&[KeyCode::KeyA])),
+                ("move_right", Shortcuts::repeating(&[KeyCode::KeyD])),
+                (
+                    "select_all",
+                    Shortcuts::single_press(&[KeyCode::KeyA]).with_ctrl(),
+                ),
+            ])),
+        );
+
+        let mut left_handed = KeymapOverrides::new();
+        left_handed.insert(
+            "move_left",
+            ActionOverride::Replace(Shortcuts::repeating(&[KeyCode::KeyJ])),
+        );
+        left_handed.insert(
+            "move_right",
+            ActionOverride::Replace(Shortcuts::repeating(&[KeyCode::KeyL])),
+        );
+        profiles.insert(
+            "Left-handed",
+            KeymapProfile::based_on("Default", left_handed),
+        );
+
+        let mut accessible = KeymapProfile::based_on("Left-handed", KeymapOverrides::new());
+        accessible
+            .keymap
+            .insert("select_all", Shortcuts::single_press(&[KeyCode::F2]));
+        profiles.insert("Accessible", accessible);
+        profiles
+    }
+
+    fn binding(keymap: &Keymap, action: &str) -> String {
+        keymap.get(action).unwrap().to_string()
+    }
+
+    #[test]
+    fn profiles_inherit_down_the_chain() {
+        let profiles = registry();
+
+        let accessible = profiles.resolve("Accessible").unwrap();
+
+        assert_eq!(binding(&accessible, "move_left"), "J", "from Left-handed");
+        assert_eq!(binding(&accessible, "select_all"), "F2", "its own");
+        assert_eq!(
+            binding(&profiles.resolve("Default").unwrap(), "move_left"),
+            "A"
+        );
+    }
+
+    #[test]
+    fn broken_chains_are_reported() {
+        let mut profiles = registry();
+        assert_eq!(
+            profiles.resolve("Classic RTS"),
+            Err(ProfileError::UnknownProfile("Classic RTS".into()))
+        );
+
+        profiles.remove("Default");
+        assert_eq!(
+            profiles.resolve("Accessible"),
+            Err(ProfileError::MissingBase {
+                profile: "Left-handed".into(),
+                based_on: "Default".into()
+            })
+        );
+
+        profiles.insert(
+            "Default",
+            KeymapProfile::based_on("Accessible", KeymapOverrides::new()),
+        );
+        assert!(matches!(
+            profiles.resolve("Accessible"),
+            Err(ProfileError::Cycle(_))
+        ));
+        assert!(profiles.select("Accessible").is_err());
+        assert_eq!(profiles.active(), None, "a failed select changes nothing");
+    }
+
+    #[test]
+    fn the_registry_round_trips_through_serde() {
+        let mut profiles = registry();
+        profiles.select("Left-handed").unwrap();
+
+        let json = serde_json::to_string(&profiles).unwrap();
+        let loaded: KeymapProfiles = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(loaded, profiles);
+        assert_eq!(loaded.active(), Some("Left-handed"));
+    }
+
+    #[test]
+    fn selecting_swaps_the_active_keymap_on_the_next_frame() {
+        let mut app = App::new();
+        app.add_plugins(KeymapProfilesPlugin);
+        *app.world_mut().resource_mut::<KeymapProfiles>() = registry();
+        app.world_mut()
+            .resource_mut::<KeymapProfiles>()
+            .select("Default")
+            .unwrap();
+        app.update();
+        assert_eq!(
+            binding(&app.world().resource::<ActiveKeymap>().0, "move_left"),
+            "A"
+        );
+
+        app.world_mut()
+            .resource_mut::<KeymapProfiles>()
+            .select("Left-handed")
+            .unwrap();
+        assert_eq!(
+            binding(&app.world().resource::<ActiveKeymap>().0, "move_left"),
+            "A",
+            "unchanged until the frame boundary"
+        );
+        app.update();
+        let active = &app.world().resource::<ActiveKeymap>().0;
+        assert_eq!(binding(active, "move_left"), "J");
+        assert_eq!(binding(active, "move_right"), "L");
+    }
+}
@@ -0,0 +1,493 @@
+//! "Press a key to bind": capturing a new chord for an action.
+
+use crate::{ModifierType, Modifiers, Shortcut, Shortcuts};
+use bevy::input::InputSystems;
+use bevy::input::keyboard::KeyCode;
+use bevy::prelude::*;
+use std::marker::PhantomData;
+
+/// The keys that count as modifiers rather than as the bound key of a chord.
+const MODIFIER_KEYS: [KeyCode; 8] = [
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::AltLeft,
+    KeyCode::AltRight,
+    KeyCode::ShiftLeft,
+    KeyCode::ShiftRight,
+    KeyCode::SuperLeft,
+    KeyCode::SuperRight,
+];
+
+/// How a capture armed with [`RebindCapture::arm`] ended.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RebindOutcome<A> {
+    /// A chord was pressed. Nothing has been written anywhere yet: show
+    /// `conflicts` to the player and commit `binding` only if they accept.
+    Captured {
+        /// The action being rebound
+        action: A,
+        /// The new binding: the captured chord, with the old binding's
+        /// trigger and the requirements it should keep (see
+        /// [`RebindCapture`])
+        binding: Shortcuts,
+        /// Other actions that the captured chord would also fire
+        conflicts: Vec<A>,
+    },
+    /// `Backspace` was pressed: the player wants the action unbound.
+    Cleared {
+        /// The action being rebound
+        action: A,
+    },
+    /// `Escape` was pressed, or [`RebindCapture::cancel`] was called: keep
+    /// the old binding.
+    Cancelled {
+        /// The action being rebound
+        action: A,
+    },
+}
+
+/// A capture in progress.
+#[derive(Debug, Clone)]
+struct Armed<A> {
+    action: A,
+    /// The action's binding when the capture was armed
+    current: Shortcuts,
+    /// Every other action's binding, for conflict reports
+    others: Vec<(A, Shortcuts)>,
+    /// A modifier pressed with nothing after it yet, bound on release
+    lone_modifier: Option<KeyCode>,
+}
+
+/// The capture mode behind a "press a key to bind" settings screen.
+///
+/// [`arm`](Self::arm) it for an action and the keymap it lives in; while armed,
+/// every frame's keyboard input is swallowed — gameplay sees no presses and
+/// the completing key never shows as held — until one of:
+///
+/// - **a chord**: any held modifiers plus one non-modifier key. Pressing a
+///   modifier on its own does nothing until it is released with no key
+///   pressed after it, which binds the modifier itself (`Shift` to sprint).
+/// - **`Escape`**: cancels, keeping the old binding.
+/// - **`Backspace`**: clears, asking for the action to be unbound.
+///
+/// The outcome is then available from [`take_outcome`](Self::take_outcome).
+/// A captured chord keeps the old binding's `RequireNotPressed`
+/// requirements on any modifier the chord does not hold — read with
+/// [`Shortcuts::modifiers`] and re-applied with [`Shortcuts::with_modifiers`] —
+/// so rebinding a control-group recall from `1` to `2` does not silently make
+/// `Ctrl + 2` fire it too. It also comes with the list of other actions it
+/// [conflicts](Shortcut::conflicts_with) with, so the screen can warn before
+/// committing.
+///
+/// Add [`RebindPlugin`] to run the capture in `PreUpdate`, or call
+/// [`update`](Self::update) yourself.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_keyboard_shortcuts::{Keymap, RebindCapture, RebindOutcome, RebindPlugin, Shortcuts};
+///
+/// #[derive(Resource, Default)]
+/// struct Controls(Keymap);
+///
+/// fn start_rebind(controls: Res<Controls>, mut capture: ResMut<RebindCapture<String>>) {
+///     capture.arm("quick_save".to_string(), &controls.0);
+/// }
+///
+/// fn finish_rebind(mut controls: ResMut<Controls>, mut capture: ResMut<RebindCapture<String>>) {
+///     match capture.take_outcome() {
+///         Some(RebindOutcome::Captured { action, binding, conflicts }) if conflicts.is_empty() => {
+///             controls.0.insert(action, binding);
+///         }
+///         Some(RebindOutcome::Captured { conflicts, .. }) => {
+///             // Ask the player whether to steal the chord from `conflicts`.
+///         }
+///         Some(RebindOutcome::Cleared { action }) => {
+///             controls.0.insert(action, Shortcuts::default());
+///         }
+///         Some(RebindOutcome::Cancelled { .. }) | None => {}
+///     }
+/// }
+///
+/// App::new()
+///     .add_plugins(RebindPlugin::<String>::default())
+///     .init_resource::<Controls>()
+///     .add_systems(Update, finish_rebind);
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct RebindCapture<A> {
+    armed: Option<Armed<A>>,
+    outcome: Option<RebindOutcome<A>>,
+}
+
+impl<A> Default for RebindCapture<A> {
+    fn default() -> Self {
+        Self {
+            armed: None,
+            outcome: None,
+        }
+    }
+}
+
+impl<A: Clone + PartialEq> RebindCapture<A> {
+    /// Starts capturing a new chord for `action`.
+    ///
+    /// `bindings` is the keymap the action belongs to: its entry for `action`
+    /// supplies the old binding, and every other entry is checked for
+    /// conflicts. Re-arming replaces a capture already in progress, and
+    /// discards any outcome not yet taken.
+    pub fn arm<'a>(&mut self, action: A, bindings: impl IntoIterator<Item = (&'a A, &'a Shortcuts)>)
+    where
+        A: 'a,
+    {
+        let mut current = Shortcuts::default();
+        let mut others = Vec::new();
+        for (bound, shortcuts) in bindings {
+            if *bound == action {
+                current = shortcuts.clone();
+            } else {
+                others.push((bound.clone(), shortcuts.clone()));
+            }
+        }
+        self.outcome = None;
+        self.armed = Some(Armed {
+            action,
+            current,
+            others,
+            lone_modifier: None,
+        });
+    }
+
+    /// Whether a capture is in progress.
+    pub fn is_armed(&self) -> bool {
+        self.armed.is_some()
+    }
+
+    /// The action being captured for, if a capture is in progress.
+    pub fn armed_action(&self) -> Option<&A> {
+        self.armed.as_ref().map(|armed| &armed.action)
+    }
+
+    /// Abandons a capture in progress, as if `Escape` had been pressed.
+    pub fn cancel(&mut self) {
+        if let Some(armed) = self.armed.take() {
+            self.outcome = Some(RebindOutcome::Cancelled {
+                action: armed.action,
+            });
+        }
+    }
+
+    /// Takes the outcome of the last capture, once it has ended.
+    pub fn take_outcome(&mut self) -> Option<RebindOutcome<A>> {
+        self.outcome.take()
+    }
+
+    /// Reads this frame's input into the capture and swallows it. Does
+    /// nothing unless armed.
+    ///
+    /// Modifiers stay held in `keys` so the chord can be read across frames;
+    /// every other key pressed while armed is reset, and all just-pressed and
+    /// just-released state is cleared.
+    pub fn update(&mut self, keys: &mut ButtonInput<KeyCode>) {
+        let Some(armed) = self.armed.as_mut() else {
+            return;
+        };
+
+        let pressed_key = keys
+            .get_just_pressed()
+            .copied()
+            .find(|key| !MODIFIER_KEYS.contains(key));
+        let completed = if let Some(key) = pressed_key {
+            Some(key)
+        } else {
+            for &key in keys.get_just_pressed() {
+                armed.lone_modifier = Some(key);
+            }
+            let released = armed.lone_modifier.filter(|&key| keys.just_released(key));
+            if keys
+                .get_just_released()
+                .any(|&key| Some(key) != armed.lone_modifier)
+            {
+                armed.lone_modifier = None;
+            }
+            released
+        };
+
+        // Swallow: nothing armed-time reaches gameplay, and only modifiers
+        // stay held so the next frame can still read them.
+        let non_modifiers: Vec<KeyCode> = keys
+            .get_pressed()
+            .copied()
+            .filter(|key| !MODIFIER_KEYS.contains(key))
+            .collect();
+        for key in non_modifiers {
+            keys.reset(key);
+        }
+
+        if let Some(key) = completed {
+            let armed = self.armed.take().expect("armed above");
+            self.outcome = Some(armed.finish(key, keys));
+        }
+        keys.clear();
+    }
+}
+
+impl<A: Clone + PartialEq> Armed<A> {
+    /// Turns the completing `key` plus the held modifiers into an outcome.
+    fn finish(self, key: KeyCode, keys: &ButtonInput<KeyCode>) -> RebindOutcome<A> {
+        match key {
+            KeyCode::Escape => {
+                return RebindOutcome::Cancelled {
+                    action: self.action,
+                };
+            }
+            KeyCode::Backspace => {
+                return RebindOutcome::Cleared {
+                    action: self.action,
+                };
+            }
+            _ => {}
+        }
+
+        // Held modifiers are required; a modifier the chord leaves up keeps
+        // whatever `RequireNotPressed` the old binding had on it.
+        let old = self.current.modifiers();
+        let requirement = |left: KeyCode, right: KeyCode, old: Option<ModifierType>| {
+            let held = [left, right]
+                .into_iter()
+                .any(|modifier| modifier != key && keys.pressed(modifier));
+            if held {
+                Some(ModifierType::RequirePressed)
+            } else {
+                old.filter(|old| *old == ModifierType::RequireNotPressed)
+            }
+        };
+        let modifiers = Modifiers::new(
+            requirement(KeyCode::ControlLeft, KeyCode::ControlRight, old.control),
+            requirement(KeyCode::AltLeft, KeyCode::AltRight, old.alt),
+            requirement(KeyCode::ShiftLeft, KeyCode::ShiftRight, old.shift),
+            requirement(KeyCode::SuperLeft, KeyCode::SuperRight, old.super_key),
+        );
+
+        let binding = Shortcuts {
+            shortcuts: vec![Shortcut {
+                input: key.into(),
+                modifiers: Modifiers::default(),
+            }],
+            ..self.current
+        }
+        .with_modifiers(modifiers);
+
+        let captured = binding.iter().next().expect("one alternative");
+        let conflicts = self
+            .others
+            .into_iter()
+            .filter(|(_, other)| other.iter().any(|s| s.conflicts_with(captured)))
+            .map(|(action, _)| action)
+            .collect();
+
+        RebindOutcome::Captured {
+            action: self.action,
+            binding,
+            conflicts,
+        }
+    }
+}
+
+/// Adds a [`RebindCapture<A>`] resource and runs it in `PreUpdate`, right
+/// after Bevy's input systems, while it is armed.
+pub struct RebindPlugin<A>(PhantomData<A>);
+
+impl<A> Default for RebindPlugin<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: Clone + PartialEq + Send + Sync + 'static> Plugin for RebindPlugin<A> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RebindCapture<A>>().add_systems(
+            PreUpdate,
+            update_rebind_capture::<A>
+                .after(InputSystems)
+                .run_if(|capture: Res<RebindCapture<A>>| capture.is_armed()),
+        );
+    }
+}
+
+fn update_rebind_capture<A: Clone + PartialEq + Send + Sync + 'static>(
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut capture: ResMut<RebindCapture<A>>,
+) {
+    capture.update(&mut keys);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn keymap() -> BTreeMap<&'static str, Shortcuts> {
+        BTreeMap::from([
+            (
+                "recall_group",
+                Shortcuts::single_press(&[KeyCode::Digit1]).without_ctrl(),
+            ),
+            (
+                "assign_group",
+                Shortcuts::single_press(&[KeyCode::Digit2]).with_ctrl(),
+            ),
+            ("sprint", Shortcuts::repeating(&[KeyCode::ShiftLeft])),
+            ("jump", Shortcuts::single_press(&[KeyCode::Space])),
+        ])
+    }
+
+    fn armed(action: &'static str) -> RebindCapture<&'static str> {
+        let mut capture = RebindCapture::default();
+        let keymap = keymap();
+        capture.arm(action, &keymap);
+        capture
+    }
+
+    /// One frame: press and release the given keys, then run the capture.
+    fn frame(
+        capture: &mut RebindCapture<&'static str>,
+        keys: &mut ButtonInput<KeyCode>,
+        press: &[KeyCode],
+        release: &[KeyCode],
+    ) {
+        for &key in release {
+            keys.release(key);
+        }
+        for &key in press {
+            keys.press(key);
+        }
+        capture.update(keys);
+    }
+
+    fn captured(outcome: Option<RebindOutcome<&'static str>>) -> (Shortcuts, Vec<&'static str>) {
+        match outcome {
+            Some(RebindOutcome::Captured {
+                binding, conflicts, ..
+            }) => (binding, conflicts),
+            other => panic!("expected a captured chord, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn captures_a_chord_and_swallows_it() {
+        let mut capture = armed("jump");
+        let mut keys = ButtonInput::<KeyCode>::default();
+
+        frame(&mut capture, &mut keys, &[KeyCode::ControlLeft], &[]);
+        assert!(
+            capture.is_armed(),
+            "a bare modifier does not complete a chord"
+        );
+        frame(&mut capture, &mut keys, &[KeyCode::KeyJ], &[]);
+
+        assert!(!capture.is_armed());
+        assert!(!keys.pressed(KeyCode::KeyJ), "gameplay never sees the key");
+        assert!(!keys.just_pressed(KeyCode::KeyJ));
+        let (binding, conflicts) = captured(capture.take_outcome());
+        assert_eq!(binding.to_string(), "Ctrl + J");
+        assert!(conflicts.is_empty());
+        assert!(!binding.repeats, "keeps the old binding's press mode");
+    }
+
+    #[test]
+    fn a_lone_modifier_binds_on_release() {
+        let mut capture = armed("jump");
+        let mut keys = ButtonInput::<KeyCode>::default();
+
+        frame(&mut capture, &mut keys, &[KeyCode::ShiftLeft], &[]);
+        frame(&mut capture, &mut keys, &[], &[]);
+        assert!(capture.is_armed());
+        frame(&mut capture, &mut keys, &[], &[KeyCode::ShiftLeft]);
+
+        let (binding, conflicts) = captured(capture.take_outcome());
+        assert_eq!(
+            binding.iter().next().unwrap().input,
+            KeyCode::ShiftLeft.into()
+        );
+        assert!(
+            binding.modifiers().none(),
+            "Shift is the key, not a modifier"
+        );
+        assert_eq!(conflicts, ["sprint"]);
+    }
+
+    #[test]
+    fn a_modifier_used_in_a_chord_is_not_bound_on_release() {
+        let mut capture = armed("jump");
+        let mut keys = ButtonInput::<KeyCode>::default();
+
+        frame(&mut capture, &mut keys, &[KeyCode::AltLeft], &[]);
+        frame(&mut capture, &mut keys, &[KeyCode::ControlLeft], &[]);
+        frame(&mut capture, &mut keys, &[], &[KeyCode::AltLeft]);
+        assert!(capture.is_armed(), "Alt was not the last key pressed");
+    }
+
+    #[test]
+    fn keeps_require_not_pressed_across_the_rebind() {
+        let mut capture = armed("recall_group");
+        let mut keys = ButtonInput::<KeyCode>::default();
+
+        frame(&mut capture, &mut keys, &[KeyCode::Digit3], &[]);
+
+        let (binding, _) = captured(capture.take_outcome());
+        assert_eq!(
+            binding.modifiers().control,
+            Some(ModifierType::RequireNotPressed)
+        );
+    }
+
+    #[test]
+    fn reports_conflicts_without_committing() {
+        let mut capture = armed("recall_group");
+        let mut keys = ButtonInput::<KeyCode>::default();
+
+        frame(&mut capture, &mut keys, &[KeyCode::Space], &[]);
+        let (_, conflicts) = captured(capture.take_outcome());
+        assert_eq!(conflicts, ["jump"]);
+
+        // `2` keeps `without_ctrl`, so it cannot fire on `Ctrl + 2`.
+        capture.arm("recall_group", &keymap());
+        frame(&mut capture, &mut keys, &[KeyCode::Digit2], &[]);
+        let (_, conflicts) = captured(capture.take_outcome());
+        assert!(conflicts.is_empty());
+    }
+
+    #[test]
+    fn escape_cancels_and_backspace_clears() {
+        let mut capture = armed("jump");
+        let mut keys = ButtonInput::<KeyCode>::default();
+        frame(&mut capture, &mut keys, &[KeyCode::Escape], &[]);
+        assert_eq!(
+            capture.take_outcome(),
+            Some(RebindOutcome::Cancelled { action: "jump" })
+        );
+        assert!(
+            !keys.just_pressed(KeyCode::Escape),
+            "does not close the menu too"
+        );
+
+        let mut capture = armed("jump");
+        frame(&mut capture, &mut keys, &[KeyCode::Backspace], &[]);
+        assert_eq!(
+            capture.take_outcome(),
+            Some(RebindOutcome::Cleared { action: "jump" })
+        );
+    }
+
+    #[test]
+    fn does_nothing_unless_armed() {
+        let mut capture = RebindCapture::<&'static str>::default();
+        let mut keys = ButtonInput::<KeyCode>::default();
+        frame(&mut capture, &mut keys, &[KeyCode::KeyJ], &[]);
+
+        assert!(keys.just_pressed(KeyCode::KeyJ));
+        assert_eq!(capture.take_outcome(), None);
+    }
+}
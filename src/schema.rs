@@ -0,0 +1,347 @@
+//! JSON Schema for keymap files, for editor validation and autocomplete.
+
+use crate::KEY_DISPLAY_MAP;
+use bevy::input::keyboard::KeyCode;
+use bevy::reflect::enums::VariantInfo;
+use bevy::reflect::{TypeInfo, Typed};
+use serde_json::{Value, json};
+
+/// The JSON Schema (draft 2020-12) of a [`Keymap`](crate::Keymap) file: a map
+/// from action name to [`Shortcuts`](crate::Shortcuts).
+///
+/// `Shortcuts`, `Shortcut`, `Modifiers`, `ModifierType`, `Trigger` and
+/// `KeyCode` are under `$defs`, with `MouseButton` and `WheelDirection` when
+/// the `mouse` feature is on, and `GamepadButton` when the `gamepad` feature
+/// is. Every key name is listed, described by the name
+/// [`Shortcut::key_str`](crate::Shortcut::key_str) displays, so editors show
+/// "Num 7" next to `Numpad7` while completing.
+///
+/// Regenerate schema files from it with the `keymap_schema` binary:
+///
+/// ```sh
+/// cargo run --bin keymap_schema -- keymap.schema.json
+/// ```
+///
+/// # Examples
+///
+/// ```rust
+/// let schema = bevy_keyboard_shortcuts::keymap_schema();
+/// assert_eq!(schema["additionalProperties"]["$ref"], "#/$defs/Shortcuts");
+/// ```
+pub fn keymap_schema() -> Value {
+    let schema = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "Keymap",
+        "description": "Action names and their keyboard bindings",
+        "type": "object",
+        "additionalProperties": { "$ref": "#/$defs/Shortcuts" },
+        "$defs": {
+            "Shortcuts": {
+                "description": "The bindings of one action; any alternative fires it",
+                "type": "object",
+                "properties": {
+                    "shortcuts": {
+                        "description": "Alternative key combinations",
+                        "type": "array",
+                        "items": { "$ref": "#/$defs/Shortcut" }
+                    },
+                    "repeats": {
+                        "description": "Fire every frame the keys are held, not just when pressed",
+                        "type": "boolean",
+                        "default": false
+                    },
+                    "trigger": { "$ref": "#/$defs/Trigger" }
+                },
+                "required": ["shortcuts"],
+                "additionalProperties": false
+            },
+            "Shortcut": shortcut(),
+            "Modifiers": {
+                "description": "Modifier requirements; an omitted modifier is ignored",
+                "type": "object",
+                "properties": {
+                    "control": modifier("Control/Command"),
+                    "alt": modifier("Alt/Option"),
+                    "shift": modifier("Shift"),
+                    "super_key": modifier("Super/Windows")
+                },
+                "additionalProperties": false
+            },
+            "ModifierType": {
+                "oneOf": [
+                    { "const": "RequirePressed", "description": "The modifier must be pressed" },
+                    { "const": "RequireNotPressed", "description": "The modifier must not be pressed" }
+                ]
+            },
+            "Trigger": {
+                "description": "What makes the binding fire",
+                "default": "Press",
+                "oneOf": [
+                    { "const": "Press", "description": "When pressed, or while held if `repeats`" },
+                    { "const": "Release", "description": "When released" },
+                    timed_trigger(
+                        "ReleaseAfterHold",
+                        "On release after holding for at least `hold_ms`",
+                        json!({ "hold_ms": milliseconds() }),
+                        &["hold_ms"],
+                    ),
+                    timed_trigger(
+                        "Tap",
+                        "On release if let go before `threshold_ms`",
+                        json!({ "threshold_ms": milliseconds() }),
+                        &["threshold_ms"],
+                    ),
+                    timed_trigger(
+                        "Hold",
+                        "Once the keys have been held for `threshold_ms`",
+                        json!({ "threshold_ms": milliseconds() }),
+                        &["threshold_ms"],
+                    ),
+                    timed_trigger(
+                        "MultiTap",
+                        "On the `taps`-th press, each within `window_ms` of the last",
+                        json!({
+                            "taps": { "type": "integer", "minimum": 1, "maximum": 255 },
+                            "window_ms": milliseconds(),
+                            "unambiguous": { "type": "boolean", "default": false }
+                        }),
+                        &["taps", "window_ms"],
+                    )
+                ]
+            },
+            "KeyCode": {
+                "description": "A physical key, named by its position on a US layout",
+                "oneOf": key_codes()
+            }
+        }
+    });
+    #[cfg(feature = "mouse")]
+    let schema = {
+        let mut schema = schema;
+        schema["$defs"]["MouseButton"] = mouse_button();
+        schema["$defs"]["WheelDirection"] = json!({
+            "description": "A mouse wheel direction; bindings fire once per notch",
+            "enum": ["Up", "Down", "Left", "Right"]
+        });
+        schema
+    };
+    #[cfg(feature = "gamepad")]
+    let schema = {
+        let mut schema = schema;
+        schema["$defs"]["Modifiers"]["properties"]["gamepad"] = json!({
+            "description": "Gamepad buttons that must be held on the same gamepad",
+            "type": "array",
+            "items": { "$ref": "#/$defs/GamepadButton" }
+        });
+        schema["$defs"]["GamepadButton"] = gamepad_button();
+        schema
+    };
+    schema
+}
+
+/// `key`, or with the `mouse` and `gamepad` features exactly one of the input
+/// fields they add.
+fn shortcut() -> Value {
+    let inputs = [
+        ("key", json!({ "$ref": "#/$defs/KeyCode" })),
+        #[cfg(feature = "mouse")]
+        ("mouse", json!({ "$ref": "#/$defs/MouseButton" })),
+        #[cfg(feature = "mouse")]
+        ("wheel", json!({ "$ref": "#/$defs/WheelDirection" })),
+        #[cfg(feature = "gamepad")]
+        ("gamepad", json!({ "$ref": "#/$defs/GamepadButton" })),
+    ];
+
+    let mut properties: serde_json::Map<String, Value> = inputs
+        .iter()
+        .map(|(name, schema)| (name.to_string(), schema.clone()))
+        .collect();
+    properties.insert("modifiers".into(), json!({ "$ref": "#/$defs/Modifiers" }));
+    let mut schema = json!({
+        "description": "A key or button with modifier requirements",
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false
+    });
+    if let [(name, _)] = inputs[..] {
+        schema["required"] = json!([name]);
+    } else {
+        schema["oneOf"] = inputs
+            .iter()
+            .map(|(name, _)| json!({ "required": [name] }))
+            .collect();
+    }
+    schema
+}
+
+#[cfg(feature = "mouse")]
+fn mouse_button() -> Value {
+    json!({
+        "description": "A mouse button",
+        "oneOf": [
+            { "const": "Left", "description": "LMB" },
+            { "const": "Right", "description": "RMB" },
+            { "const": "Middle", "description": "MMB" },
+            { "const": "Back", "description": "Mouse4" },
+            { "const": "Forward", "description": "Mouse5" },
+            {
+                "description": "Any other button, by number",
+                "type": "object",
+                "properties": { "Other": { "type": "integer", "minimum": 0, "maximum": 65535 } },
+                "required": ["Other"],
+                "additionalProperties": false
+            }
+        ]
+    })
+}
+
+#[cfg(feature = "gamepad")]
+fn gamepad_button() -> Value {
+    use crate::GamepadGlyphs;
+    use bevy::input::gamepad::GamepadButton;
+
+    let mut buttons: Vec<Value> = GamepadButton::all()
+        .into_iter()
+        .map(|button| {
+            let names = [
+                GamepadGlyphs::Xbox,
+                GamepadGlyphs::PlayStation,
+                GamepadGlyphs::Nintendo,
+            ]
+            .map(|glyphs| glyphs.name(button));
+            json!({ "const": format!("{button:?}"), "description": names.join(" / ") })
+        })
+        .collect();
+    buttons.push(json!({
+        "description": "Any other button, by number",
+        "type": "object",
+        "properties": { "Other": { "type": "integer", "minimum": 0, "maximum": 255 } },
+        "required": ["Other"],
+        "additionalProperties": false
+    }));
+    json!({
+        "description": "A gamepad button, named by its position on the controller",
+        "oneOf": buttons
+    })
+}
+
+/// Serialized keymaps write ignored modifiers as `null`.
+fn modifier(name: &str) -> Value {
+    json!({
+        "description": format!("{name} requirement"),
+        "anyOf": [{ "$ref": "#/$defs/ModifierType" }, { "type": "null" }]
+    })
+}
+
+fn milliseconds() -> Value {
+    json!({ "type": "integer", "minimum": 0, "maximum": u32::MAX })
+}
+
+/// A struct variant of `Trigger`, externally tagged as serde writes it.
+fn timed_trigger(name: &str, description: &str, properties: Value, required: &[&str]) -> Value {
+    json!({
+        "description": description,
+        "type": "object",
+        "properties": {
+            name: {
+                "type": "object",
+                "properties": properties,
+                "required": required,
+                "additionalProperties": false
+            }
+        },
+        "required": [name],
+        "additionalProperties": false
+    })
+}
+
+/// Every `KeyCode` a file can name, described by its display name.
+fn key_codes() -> Vec<Value> {
+    key_code_names()
+        .map(|name| {
+            let display = KEY_DISPLAY_MAP.get(name).copied().unwrap_or(name);
+            json!({ "const": name, "description": display })
+        })
+        .collect()
+}
+
+/// The names of the unit `KeyCode` variants, which serialize as their
+/// names. `Unidentified` carries platform scan codes and is left out.
+pub(crate) fn key_code_names() -> impl Iterator<Item = &'static str> {
+    let TypeInfo::Enum(info) = KeyCode::type_info() else {
+        unreachable!("KeyCode is an enum");
+    };
+    info.iter().filter_map(|variant| match variant {
+        VariantInfo::Unit(unit) => Some(unit.name()),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Trigger;
+
+    fn consts(schema: &Value) -> Vec<String> {
+        schema["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|option| match &option["const"] {
+                Value::String(name) => name.clone(),
+                // A struct variant's tag is its only property.
+                _ => option["required"][0].as_str().unwrap().to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn key_codes_are_listed_with_their_display_names() {
+        let keys = keymap_schema()["$defs"]["KeyCode"].clone();
+        let names = consts(&keys);
+
+        assert!(names.len() > 150, "only {} keys", names.len());
+        assert!(names.contains(&"Numpad7".to_string()));
+        assert!(!names.contains(&"Unidentified".to_string()));
+
+        let numpad_7 = keys["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|option| option["const"] == "Numpad7")
+            .unwrap();
+        assert_eq!(numpad_7["description"], "Num 7");
+    }
+
+    #[test]
+    fn every_trigger_variant_is_described() {
+        let TypeInfo::Enum(info) = Trigger::type_info() else {
+            unreachable!();
+        };
+        let variants: Vec<String> = info.variant_names().iter().map(|s| s.to_string()).collect();
+
+        assert_eq!(consts(&keymap_schema()["$defs"]["Trigger"]), variants);
+    }
+
+    #[test]
+    fn config_keys_match_the_serde_field_names() {
+        let schema = keymap_schema();
+        let shortcuts = serde_json::to_value(
+            crate::Shortcuts::single_press(&[KeyCode::KeyS])
+                .with_ctrl()
+                .without_shift(),
+        )
+        .unwrap();
+
+        for (def, value) in [
+            ("Shortcuts", &shortcuts),
+            ("Shortcut", &shortcuts["shortcuts"][0]),
+            ("Modifiers", &shortcuts["shortcuts"][0]["modifiers"]),
+        ] {
+            let properties = schema["$defs"][def]["properties"].as_object().unwrap();
+            for field in value.as_object().unwrap().keys() {
+                assert!(properties.contains_key(field), "{def} has no {field:?}");
+            }
+        }
+    }
+}
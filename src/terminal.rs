@@ -0,0 +1,384 @@
+//! Keyboard state from crossterm events, for keymaps driving a terminal UI.
+
+use crate::input::{DIGIT_KEYS, LETTER_KEYS};
+use crate::{KeySet, KeyState};
+use bevy::input::keyboard::KeyCode;
+use crossterm::event::{
+    Event, KeyCode as TermKey, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, ModifierKeyCode,
+};
+
+/// Keyboard state built from crossterm key events, so the same [`Shortcuts`]
+/// and keymap files drive a terminal console as drive the game.
+///
+/// Characters are read as the US-layout keys that type them, matching the
+/// physical [`KeyCode`]s of keymap files: `s` is `KeyS`, `S` is `Shift` +
+/// `KeyS` and `?` is `Shift` + `Slash`. Modifiers come from each event's
+/// [`KeyModifiers`].
+///
+/// Feed it every event of a frame with [`TerminalKeys::handle`], match
+/// shortcuts against it, then call [`TerminalKeys::clear`].
+///
+/// Most terminals only report presses. By default a press then counts as a
+/// tap: held for the frame it arrives in and released at the next
+/// [`clear`](TerminalKeys::clear), so single-press and release triggers work
+/// but a held key reads as the terminal's key repeat. Terminals that support
+/// the kitty keyboard protocol report releases and repeats once
+/// `KeyboardEnhancementFlags::REPORT_EVENT_TYPES` is pushed; use
+/// [`TerminalKeys::with_release_events`] then, and held keys stay held.
+///
+/// [`Shortcuts`]: crate::Shortcuts
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy::prelude::KeyCode;
+/// use bevy_keyboard_shortcuts::{Shortcuts, TerminalKeys};
+/// use crossterm::event::{Event, KeyCode as TermKey, KeyEvent, KeyModifiers};
+///
+/// let kick = Shortcuts::single_press(&[KeyCode::KeyK]).with_ctrl();
+/// let mut keys = TerminalKeys::default();
+///
+/// keys.handle(&Event::Key(KeyEvent::new(TermKey::Char('k'), KeyModifiers::CONTROL)));
+/// assert!(kick.pressed(&keys));
+/// keys.clear();
+/// assert!(!kick.pressed(&keys));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TerminalKeys {
+    keys: KeySet,
+    release_events: bool,
+}
+
+impl TerminalKeys {
+    /// Expects the terminal to report releases, keeping keys held until
+    /// their release arrives (chainable).
+    pub fn with_release_events(mut self) -> Self {
+        self.release_events = true;
+        self
+    }
+
+    /// Applies one terminal event. Key events press and release keys, and
+    /// losing focus releases everything; other events are ignored.
+    pub fn handle(&mut self, event: &Event) {
+        match event {
+            Event::Key(key) => self.handle_key(key),
+            Event::FocusLost => self.keys.release_all(),
+            _ => {}
+        }
+    }
+
+    /// Applies one key event.
+    pub fn handle_key(&mut self, event: &KeyEvent) {
+        let Some((key, implied_shift)) = translate(event) else {
+            return;
+        };
+        let mut modifiers = event.modifiers;
+        if implied_shift {
+            modifiers |= KeyModifiers::SHIFT;
+        }
+        // Modifiers first, so a chord's key goes down (or up) under the
+        // modifiers it was typed with.
+        self.sync_modifiers(modifiers);
+        match event.kind {
+            KeyEventKind::Press | KeyEventKind::Repeat => self.keys.press(key),
+            KeyEventKind::Release => self.keys.release(key),
+        }
+    }
+
+    /// Ends the frame. Without release events, the keys pressed this frame
+    /// are released here.
+    pub fn clear(&mut self) {
+        self.keys.clear();
+        if !self.release_events {
+            self.keys.release_all();
+        }
+    }
+
+    /// Presses or releases modifier keys to agree with an event's flags.
+    fn sync_modifiers(&mut self, modifiers: KeyModifiers) {
+        for (flag, left, right) in [
+            (
+                KeyModifiers::CONTROL,
+                KeyCode::ControlLeft,
+                KeyCode::ControlRight,
+            ),
+            (KeyModifiers::ALT, KeyCode::AltLeft, KeyCode::AltRight),
+            (KeyModifiers::SHIFT, KeyCode::ShiftLeft, KeyCode::ShiftRight),
+            (KeyModifiers::SUPER, KeyCode::SuperLeft, KeyCode::SuperRight),
+        ] {
+            if !modifiers.contains(flag) {
+                self.keys.release(left);
+                self.keys.release(right);
+            } else if !self.keys.pressed(left) && !self.keys.pressed(right) {
+                self.keys.press(left);
+            }
+        }
+    }
+}
+
+impl KeyState for TerminalKeys {
+    fn pressed(&self, key: KeyCode) -> bool {
+        self.keys.pressed(key)
+    }
+
+    fn just_pressed(&self, key: KeyCode) -> bool {
+        self.keys.just_pressed(key)
+    }
+
+    fn just_released(&self, key: KeyCode) -> bool {
+        self.keys.just_released(key)
+    }
+
+    fn get_just_pressed(&self) -> impl Iterator<Item = KeyCode> {
+        self.keys.get_just_pressed()
+    }
+
+    fn get_just_released(&self) -> impl Iterator<Item = KeyCode> {
+        self.keys.get_just_released()
+    }
+}
+
+/// The key an event is for, and whether typing it took Shift.
+fn translate(event: &KeyEvent) -> Option<(KeyCode, bool)> {
+    let keypad = event.state.contains(KeyEventState::KEYPAD);
+    let key = match event.code {
+        TermKey::Char(c) if keypad => return keypad_key(c).map(|key| (key, false)),
+        TermKey::Char(c) => return char_key(c),
+        TermKey::BackTab => return Some((KeyCode::Tab, true)),
+        TermKey::Enter if keypad => KeyCode::NumpadEnter,
+        TermKey::Backspace => KeyCode::Backspace,
+        TermKey::Enter => KeyCode::Enter,
+        TermKey::Left => KeyCode::ArrowLeft,
+        TermKey::Right => KeyCode::ArrowRight,
+        TermKey::Up => KeyCode::ArrowUp,
+        TermKey::Down => KeyCode::ArrowDown,
+        TermKey::Home => KeyCode::Home,
+        TermKey::End => KeyCode::End,
+        TermKey::PageUp => KeyCode::PageUp,
+        TermKey::PageDown => KeyCode::PageDown,
+        TermKey::Tab => KeyCode::Tab,
+        TermKey::Delete => KeyCode::Delete,
+        TermKey::Insert => KeyCode::Insert,
+        TermKey::Esc => KeyCode::Escape,
+        TermKey::CapsLock => KeyCode::CapsLock,
+        TermKey::ScrollLock => KeyCode::ScrollLock,
+        TermKey::NumLock => KeyCode::NumLock,
+        TermKey::PrintScreen => KeyCode::PrintScreen,
+        TermKey::Pause => KeyCode::Pause,
+        TermKey::Menu => KeyCode::ContextMenu,
+        TermKey::F(n) => function_key(n)?,
+        TermKey::Modifier(modifier) => match modifier {
+            ModifierKeyCode::LeftShift => KeyCode::ShiftLeft,
+            ModifierKeyCode::RightShift => KeyCode::ShiftRight,
+            ModifierKeyCode::LeftControl => KeyCode::ControlLeft,
+            ModifierKeyCode::RightControl => KeyCode::ControlRight,
+            ModifierKeyCode::LeftAlt => KeyCode::AltLeft,
+            ModifierKeyCode::RightAlt => KeyCode::AltRight,
+            ModifierKeyCode::LeftSuper => KeyCode::SuperLeft,
+            ModifierKeyCode::RightSuper => KeyCode::SuperRight,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some((key, false))
+}
+
+/// The US-layout key that types `c`, and whether it takes Shift.
+fn char_key(c: char) -> Option<(KeyCode, bool)> {
+    if c.is_ascii_lowercase() {
+        return Some((LETTER_KEYS[(c as u8 - b'a') as usize], false));
+    }
+    if c.is_ascii_uppercase() {
+        return Some((LETTER_KEYS[(c as u8 - b'A') as usize], true));
+    }
+    if c.is_ascii_digit() {
+        return Some((DIGIT_KEYS[(c as u8 - b'0') as usize], false));
+    }
+    if let Some(shifted) = ")!@#$%^&*(".find(c) {
+        return Some((DIGIT_KEYS[shifted], true));
+    }
+    let (key, shift) = match c {
+        ' ' => (KeyCode::Space, false),
+        '-' => (KeyCode::Minus, false),
+        '_' => (KeyCode::Minus, true),
+        '=' => (KeyCode::Equal, false),
+        '+' => (KeyCode::Equal, true),
+        '[' => (KeyCode::BracketLeft, false),
+        '{' => (KeyCode::BracketLeft, true),
+        ']' => (KeyCode::BracketRight, false),
+        '}' => (KeyCode::BracketRight, true),
+        '\\' => (KeyCode::Backslash, false),
+        '|' => (KeyCode::Backslash, true),
+        ';' => (KeyCode::Semicolon, false),
+        ':' => (KeyCode::Semicolon, true),
+        '\'' => (KeyCode::Quote, false),
+        '"' => (KeyCode::Quote, true),
+        ',' => (KeyCode::Comma, false),
+        '<' => (KeyCode::Comma, true),
+        '.' => (KeyCode::Period, false),
+        '>' => (KeyCode::Period, true),
+        '/' => (KeyCode::Slash, false),
+        '?' => (KeyCode::Slash, true),
+        '`' => (KeyCode::Backquote, false),
+        '~' => (KeyCode::Backquote, true),
+        _ => return None,
+    };
+    Some((key, shift))
+}
+
+/// The numeric keypad key that types `c`.
+fn keypad_key(c: char) -> Option<KeyCode> {
+    Some(match c {
+        '0' => KeyCode::Numpad0,
+        '1' => KeyCode::Numpad1,
+        '2' => KeyCode::Numpad2,
+        '3' => KeyCode::Numpad3,
+        '4' => KeyCode::Numpad4,
+        '5' => KeyCode::Numpad5,
+        '6' => KeyCode::Numpad6,
+        '7' => KeyCode::Numpad7,
+        '8' => KeyCode::Numpad8,
+        '9' => KeyCode::Numpad9,
+        '+' => KeyCode::NumpadAdd,
+        '-' => KeyCode::NumpadSubtract,
+        '*' => KeyCode::NumpadMultiply,
+        '/' => KeyCode::NumpadDivide,
+        '.' => KeyCode::NumpadDecimal,
+        '=' => KeyCode::NumpadEqual,
+        ',' => KeyCode::NumpadComma,
+        _ => return None,
+    })
+}
+
+fn function_key(n: u8) -> Option<KeyCode> {
+    const KEYS: [KeyCode; 24] = [
+        KeyCode::F1,
+        KeyCode::F2,
+        KeyCode::F3,
+        KeyCode::F4,
+        KeyCode::F5,
+        KeyCode::F6,
+        KeyCode::F7,
+        KeyCode::F8,
+        KeyCode::F9,
+        KeyCode::F10,
+        KeyCode::F11,
+        KeyCode::F12,
+        KeyCode::F13,
+        KeyCode::F14,
+        KeyCode::F15,
+        KeyCode::F16,
+        KeyCode::F17,
+        KeyCode::F18,
+        KeyCode::F19,
+        KeyCode::F20,
+        KeyCode::F21,
+        KeyCode::F22,
+        KeyCode::F23,
+        KeyCode::F24,
+    ];
+    KEYS.get(usize::from(n).checked_sub(1)?).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Keymap, Shortcuts};
+
+    fn press(code: TermKey, modifiers: KeyModifiers) -> Event {
+        Event::Key(KeyEvent::new(code, modifiers))
+    }
+
+    fn release(code: TermKey, modifiers: KeyModifiers) -> Event {
+        Event::Key(KeyEvent::new_with_kind(
+            code,
+            modifiers,
+            KeyEventKind::Release,
+        ))
+    }
+
+    #[test]
+    fn characters_read_as_the_keys_that_type_them() {
+        let redo = Shortcuts::single_press(&[KeyCode::KeyZ])
+            .with_ctrl()
+            .with_shift();
+        let undo = Shortcuts::single_press(&[KeyCode::KeyZ])
+            .with_ctrl()
+            .without_shift();
+        let help = Shortcuts::single_press(&[KeyCode::Slash]).with_shift();
+        let mut keys = TerminalKeys::default();
+
+        // Terminals report Ctrl+Shift+Z as an uppercase Z, Shift flag or not.
+        keys.handle(&press(TermKey::Char('Z'), KeyModifiers::CONTROL));
+        assert!(redo.pressed(&keys));
+        assert!(!undo.pressed(&keys));
+        keys.clear();
+
+        keys.handle(&press(TermKey::Char('z'), KeyModifiers::CONTROL));
+        assert!(undo.pressed(&keys));
+        assert!(!redo.pressed(&keys));
+        keys.clear();
+
+        keys.handle(&press(TermKey::Char('?'), KeyModifiers::NONE));
+        assert!(help.pressed(&keys));
+    }
+
+    #[test]
+    fn presses_are_taps_unless_releases_are_reported() {
+        let menu = Shortcuts::on_release(&[KeyCode::Escape]);
+        let walk = Shortcuts::repeating(&[KeyCode::KeyW]);
+        let mut taps = TerminalKeys::default();
+
+        taps.handle(&press(TermKey::Esc, KeyModifiers::NONE));
+        assert!(!menu.pressed(&taps));
+        taps.clear();
+        assert!(menu.pressed(&taps), "released the frame after");
+
+        let mut held = TerminalKeys::default().with_release_events();
+        held.handle(&press(TermKey::Char('w'), KeyModifiers::NONE));
+        held.clear();
+        let repeat =
+            KeyEvent::new_with_kind(TermKey::Char('w'), KeyModifiers::NONE, KeyEventKind::Repeat);
+        held.handle(&Event::Key(repeat));
+        assert!(walk.pressed(&held));
+        assert!(!held.just_pressed(KeyCode::KeyW), "repeats do not re-press");
+        held.clear();
+        assert!(walk.pressed(&held), "still held");
+
+        held.handle(&release(TermKey::Char('w'), KeyModifiers::NONE));
+        assert!(!walk.pressed(&held));
+
+        held.handle(&press(TermKey::Char('w'), KeyModifiers::NONE));
+        held.handle(&Event::FocusLost);
+        assert!(!walk.pressed(&held), "focus loss releases everything");
+    }
+
+    #[test]
+    fn a_keymap_evaluates_as_it_does_in_bevy() {
+        let keymap = Keymap::from_iter([
+            (
+                "kick_player",
+                Shortcuts::single_press(&[KeyCode::KeyK]).with_ctrl(),
+            ),
+            ("toggle_log", Shortcuts::single_press(&[KeyCode::F2])),
+            (
+                "next_page",
+                Shortcuts::single_press(&[KeyCode::Tab]).with_shift(),
+            ),
+        ]);
+        let mut keys = TerminalKeys::default().with_release_events();
+
+        keys.handle(&press(TermKey::F(2), KeyModifiers::NONE));
+        assert!(keymap.pressed("toggle_log", &keys));
+        keys.clear();
+
+        keys.handle(&press(TermKey::Char('k'), KeyModifiers::CONTROL));
+        assert!(keymap.pressed("kick_player", &keys));
+        keys.clear();
+        keys.handle(&release(TermKey::Char('k'), KeyModifiers::NONE));
+        assert!(!keys.pressed(KeyCode::ControlLeft), "Ctrl let go");
+
+        keys.handle(&press(TermKey::BackTab, KeyModifiers::SHIFT));
+        assert!(keymap.pressed("next_page", &keys));
+    }
+}
@@ -0,0 +1,702 @@
+//! Trigger modes and the per-binding state that timed triggers need.
+
+use crate::input::ButtonSource;
+use crate::{KeyState, Shortcuts};
+#[cfg(feature = "gamepad")]
+use bevy::input::gamepad::Gamepad;
+#[cfg(feature = "mouse")]
+use bevy::input::mouse::MouseButton;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// What makes a [`Shortcuts`] binding fire.
+///
+/// `Press` and `Release` can be read straight off [`ButtonInput`] with
+/// [`Shortcuts::pressed`]. The timed triggers need to know how long a chord
+/// has been held, which `ButtonInput` does not track — drive those through a
+/// [`ShortcutState`].
+///
+/// Durations are whole milliseconds so that a config file reads
+/// `hold_ms: 800` rather than a serialized `Duration`.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum Trigger {
+    /// Fires when the chord is pressed — every frame it is held if the binding
+    /// `repeats`, otherwise only on the frame it goes down (DEFAULT).
+    #[default]
+    Press,
+    /// Fires on the frame the key comes up while its modifiers still match.
+    Release,
+    /// Fires on release, but only if the chord was held for at least
+    /// `hold_ms` — a charge attack or a press-and-hold confirmation button.
+    ReleaseAfterHold {
+        /// Minimum hold, in milliseconds
+        hold_ms: u32,
+    },
+    /// Fires on release if the chord was let go *before* `threshold_ms`.
+    ///
+    /// Pair it with a [`Trigger::Hold`] on the same key and threshold to give
+    /// one key a tap action and a hold action: every press fires exactly one
+    /// of the two.
+    Tap {
+        /// Longest hold that still counts as a tap, in milliseconds (exclusive)
+        threshold_ms: u32,
+    },
+    /// Fires once, on the frame the chord has been held for `threshold_ms`,
+    /// without waiting for the release.
+    Hold {
+        /// Hold needed to fire, in milliseconds
+        threshold_ms: u32,
+    },
+    /// Fires on the `taps`-th successive press of the same alternative, each
+    /// within `window_ms` of the one before — a double-tap dash, `Esc Esc`.
+    ///
+    /// With `unambiguous` set it instead waits for the window after the last
+    /// press to run out and fires only if exactly `taps` presses landed. That
+    /// is how the single-tap action of a key that also has a double-tap
+    /// binding avoids firing on the first half of every double tap: bind it as
+    /// `taps: 1, unambiguous: true`.
+    MultiTap {
+        /// Number of presses needed, at least one
+        #[serde(deserialize_with = "at_least_one_tap")]
+        taps: u8,
+        /// Longest gap between two presses of the sequence, in milliseconds
+        window_ms: u32,
+        /// Wait for the window to expire and require exactly `taps` presses
+        #[serde(default)]
+        unambiguous: bool,
+    },
+}
+
+impl Trigger {
+    /// Returns `true` for the default [`Trigger::Press`].
+    pub fn is_press(&self) -> bool {
+        *self == Trigger::Press
+    }
+
+    /// Returns `true` if this trigger depends on hold timing and so has to be
+    /// evaluated through a [`ShortcutState`].
+    pub fn is_timed(&self) -> bool {
+        !matches!(self, Trigger::Press | Trigger::Release)
+    }
+}
+
+/// Rejects `taps: 0` in config files, a multi-tap that could never fire.
+fn at_least_one_tap<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<u8, D::Error> {
+    match u8::deserialize(deserializer)? {
+        0 => Err(serde::de::Error::invalid_value(
+            serde::de::Unexpected::Unsigned(0),
+            &"at least one tap",
+        )),
+        taps => Ok(taps),
+    }
+}
+
+/// Converts a builder duration to the millisecond form [`Trigger`] stores,
+/// saturating rather than wrapping for absurdly long holds.
+pub(crate) fn duration_ms(duration: Duration) -> u32 {
+    u32::try_from(duration.as_millis()).unwrap_or(u32::MAX)
+}
+
+/// Hold tracking for one [`Shortcuts`] binding.
+///
+/// Call [`update`](Self::update) once per frame with the binding, the keyboard
+/// and the clock; it returns whether the binding fired this frame for any
+/// [`Trigger`], timed or not. Keep one state per binding — typically alongside
+/// the `Shortcuts` in the same resource or component.
+///
+/// A chord is *held* from the frame one of its alternatives starts matching
+/// (key and modifiers) until it stops. It counts as *released* only if the key
+/// itself comes up while the modifiers still match; letting go of a required
+/// modifier first abandons the hold instead, so dropping `Shift` mid-charge
+/// does not fire a `Shift + Space` charge attack.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_keyboard_shortcuts::{ShortcutState, Shortcuts};
+/// use std::time::Duration;
+///
+/// #[derive(Resource)]
+/// struct ChargeAttack {
+///     binding: Shortcuts,
+///     state: ShortcutState,
+/// }
+///
+/// fn charge_attack(
+///     keyboard: Res<ButtonInput<KeyCode>>,
+///     time: Res<Time>,
+///     mut charge: ResMut<ChargeAttack>,
+/// ) {
+///     let ChargeAttack { binding, state } = &mut *charge;
+///     if state.update(binding, &keyboard, &time) {
+///         // Released after at least a second: unleash it.
+///     } else if let Some(held) = state.held_duration() {
+///         // Still charging — draw a meter from `held`.
+///     }
+/// }
+///
+/// let binding = Shortcuts::released_after_hold(&[KeyCode::Space], Duration::from_secs(1));
+/// ```
+#[derive(Reflect, Debug, Clone, Default)]
+pub struct ShortcutState {
+    /// Index of the alternative currently held, if any
+    held_alternative: Option<usize>,
+    /// How long that alternative has been held
+    held_for: Duration,
+    /// Hold length of a chord released this frame
+    released_after: Option<Duration>,
+    /// Whether a `Hold` trigger already fired for the current hold
+    hold_fired: bool,
+    /// Alternative the current tap sequence is on, if one is running
+    tap_alternative: Option<usize>,
+    /// Presses counted in the current tap sequence
+    tap_count: u8,
+    /// Time since the last press of the current tap sequence
+    since_tap: Duration,
+    /// Whether the binding fired on the last update
+    triggered: bool,
+}
+
+impl ShortcutState {
+    /// Advances the state by one frame and returns whether `shortcuts` fired.
+    ///
+    /// # Arguments
+    ///
+    /// * `shortcuts` - The binding this state tracks
+    /// * `keys` - The current keyboard state, such as Bevy's `ButtonInput<KeyCode>`
+    /// * `time` - The clock whose `delta` is added to the hold duration
+    pub fn update<T: Default>(
+        &mut self,
+        shortcuts: &Shortcuts,
+        keys: &impl KeyState,
+        time: &Time<T>,
+    ) -> bool {
+        self.update_in(shortcuts, keys, time)
+    }
+
+    /// [`ShortcutState::update`], for bindings with mouse button
+    /// alternatives: hold a mouse button to charge, double-click, …
+    #[cfg(feature = "mouse")]
+    pub fn update_with_mouse<T: Default>(
+        &mut self,
+        shortcuts: &Shortcuts,
+        keys: &impl KeyState,
+        mouse: &ButtonInput<MouseButton>,
+        time: &Time<T>,
+    ) -> bool {
+        self.update_in(shortcuts, &(keys, mouse), time)
+    }
+
+    /// [`ShortcutState::update`], for the gamepad alternatives of a binding
+    /// as pressed on `gamepad`. Keep one state per gamepad (and one for the
+    /// keyboard) so holds and taps on different devices are timed apart.
+    #[cfg(feature = "gamepad")]
+    pub fn update_on_gamepad<T: Default>(
+        &mut self,
+        shortcuts: &Shortcuts,
+        gamepad: &Gamepad,
+        time: &Time<T>,
+    ) -> bool {
+        self.update_in(shortcuts, gamepad, time)
+    }
+
+    fn update_in<T: Default>(
+        &mut self,
+        shortcuts: &Shortcuts,
+        keys: &impl ButtonSource,
+        time: &Time<T>,
+    ) -> bool {
+        self.released_after = None;
+        let (tapped, expired_taps) = self.count_taps(shortcuts, keys, time.delta());
+
+        if let Some(index) = self.held_alternative {
+            match shortcuts.shortcuts.get(index) {
+                Some(shortcut) if shortcut.pressed_in(keys) => {
+                    self.held_for += time.delta();
+                }
+                Some(shortcut) => {
+                    if shortcut.just_released_in(keys) {
+                        self.released_after = Some(self.held_for);
+                    }
+                    self.held_alternative = None;
+                }
+                // The binding was edited under us; drop the stale hold.
+                None => self.held_alternative = None,
+            }
+        }
+
+        if self.held_alternative.is_none() {
+            self.held_alternative = shortcuts.shortcuts.iter().position(|s| s.pressed_in(keys));
+            self.held_for = Duration::ZERO;
+            self.hold_fired = false;
+        }
+
+        self.triggered = match shortcuts.trigger {
+            Trigger::Press | Trigger::Release => shortcuts.pressed_in(keys),
+            Trigger::ReleaseAfterHold { hold_ms } => self
+                .released_after
+                .is_some_and(|held| held >= Duration::from_millis(hold_ms.into())),
+            Trigger::Tap { threshold_ms } => self
+                .released_after
+                .is_some_and(|held| held < Duration::from_millis(threshold_ms.into())),
+            Trigger::Hold { threshold_ms } => {
+                let crossed = !self.hold_fired
+                    && self
+                        .held_duration()
+                        .is_some_and(|held| held >= Duration::from_millis(threshold_ms.into()));
+                self.hold_fired |= crossed;
+                crossed
+            }
+            Trigger::MultiTap {
+                taps,
+                unambiguous: false,
+                ..
+            } => {
+                let reached = tapped && self.tap_count == taps;
+                if reached {
+                    // A third tap starts a fresh sequence rather than firing a
+                    // double-tap again.
+                    self.tap_alternative = None;
+                    self.tap_count = 0;
+                }
+                reached
+            }
+            Trigger::MultiTap {
+                taps,
+                unambiguous: true,
+                ..
+            } => expired_taps == Some(taps),
+        };
+        self.triggered
+    }
+
+    /// Advances the tap sequence by one frame. Returns whether a press was
+    /// counted this frame, and the final count of a sequence that ended.
+    fn count_taps(
+        &mut self,
+        shortcuts: &Shortcuts,
+        keys: &impl ButtonSource,
+        delta: Duration,
+    ) -> (bool, Option<u8>) {
+        let window = match shortcuts.trigger {
+            Trigger::MultiTap { window_ms, .. } => Duration::from_millis(window_ms.into()),
+            _ => return (false, None),
+        };
+        let mut expired = None;
+
+        if self.tap_alternative.is_some() {
+            self.since_tap += delta;
+            if self.since_tap > window {
+                expired = Some(self.tap_count);
+                self.tap_alternative = None;
+                self.tap_count = 0;
+            }
+        }
+
+        let Some(index) = shortcuts
+            .shortcuts
+            .iter()
+            .position(|s| s.just_pressed_in(keys))
+        else {
+            return (false, expired);
+        };
+        if self.tap_alternative == Some(index) {
+            self.tap_count = self.tap_count.saturating_add(1);
+        } else {
+            // Switching alternatives ends the sequence: `A A` is a double tap,
+            // `A Left` is not.
+            if self.tap_alternative.is_some() {
+                expired = Some(self.tap_count);
+            }
+            self.tap_alternative = Some(index);
+            self.tap_count = 1;
+        }
+        self.since_tap = Duration::ZERO;
+        (true, expired)
+    }
+
+    /// Whether the binding fired on the last [`update`](Self::update).
+    pub fn triggered(&self) -> bool {
+        self.triggered
+    }
+
+    /// How long the chord has been held, or `None` if it is not held.
+    ///
+    /// This is zero on the frame the chord goes down and grows by the clock's
+    /// delta on every later update.
+    pub fn held_duration(&self) -> Option<Duration> {
+        self.held_alternative.map(|_| self.held_for)
+    }
+
+    /// How long the chord had been held, on the frame it was released.
+    ///
+    /// `None` on every other frame, including one where the hold was abandoned
+    /// by releasing a modifier first.
+    pub fn released_after(&self) -> Option<Duration> {
+        self.released_after
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(time: &mut Time, keys: &mut ButtonInput<KeyCode>, ms: u64) {
+        keys.clear();
+        time.advance_by(Duration::from_millis(ms));
+    }
+
+    #[test]
+    fn held_duration_accumulates_frame_deltas() {
+        let binding = Shortcuts::repeating(&[KeyCode::KeyW]);
+        let mut state = ShortcutState::default();
+        let mut keys = ButtonInput::<KeyCode>::default();
+        let mut time = Time::<()>::default();
+
+        state.update(&binding, &keys, &time);
+        assert_eq!(state.held_duration(), None);
+
+        keys.press(KeyCode::KeyW);
+        state.update(&binding, &keys, &time);
+        assert_eq!(state.held_duration(), Some(Duration::ZERO));
+
+        frame(&mut time, &mut keys, 250);
+        state.update(&binding, &keys, &time);
+        frame(&mut time, &mut keys, 250);
+        state.update(&binding, &keys, &time);
+        assert_eq!(state.held_duration(), Some(Duration::from_millis(500)));
+
+        frame(&mut time, &mut keys, 16);
+        keys.release(KeyCode::KeyW);
+        state.update(&binding, &keys, &time);
+        assert_eq!(state.held_duration(), None);
+        assert_eq!(state.released_after(), Some(Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn release_after_hold_fires_only_past_the_threshold() {
+        let binding = Shortcuts::released_after_hold(&[KeyCode::Space], Duration::from_millis(800));
+        let mut state = ShortcutState::default();
+        let mut keys = ButtonInput::<KeyCode>::default();
+        let mut time = Time::<()>::default();
+
+        // A short press: released at 500ms.
+        keys.press(KeyCode::Space);
+        assert!(!state.update(&binding, &keys, &time));
+        frame(&mut time, &mut keys, 500);
+        assert!(!state.update(&binding, &keys, &time));
+        frame(&mut time, &mut keys, 16);
+        keys.release(KeyCode::Space);
+        assert!(!state.update(&binding, &keys, &time), "released too early");
+
+        // A full charge: held past 800ms, fires on the release frame only.
+        frame(&mut time, &mut keys, 16);
+        keys.press(KeyCode::Space);
+        state.update(&binding, &keys, &time);
+        frame(&mut time, &mut keys, 900);
+        assert!(
+            !state.update(&binding, &keys, &time),
+            "holding does not fire"
+        );
+        frame(&mut time, &mut keys, 16);
+        keys.release(KeyCode::Space);
+        assert!(state.update(&binding, &keys, &time));
+        assert!(state.triggered());
+
+        frame(&mut time, &mut keys, 16);
+        assert!(!state.update(&binding, &keys, &time));
+    }
+
+    #[test]
+    fn dropping_a_required_modifier_abandons_the_hold() {
+        let binding = Shortcuts::released_after_hold(&[KeyCode::Space], Duration::from_millis(100))
+            .with_shift();
+        let mut state = ShortcutState::default();
+        let mut keys = ButtonInput::<KeyCode>::default();
+        let mut time = Time::<()>::default();
+
+        keys.press(KeyCode::ShiftLeft);
+        keys.press(KeyCode::Space);
+        state.update(&binding, &keys, &time);
+        frame(&mut time, &mut keys, 200);
+        state.update(&binding, &keys, &time);
+
+        frame(&mut time, &mut keys, 16);
+        keys.release(KeyCode::ShiftLeft);
+        assert!(!state.update(&binding, &keys, &time));
+        assert_eq!(state.released_after(), None);
+
+        frame(&mut time, &mut keys, 16);
+        keys.release(KeyCode::Space);
+        assert!(
+            !state.update(&binding, &keys, &time),
+            "the hold was abandoned"
+        );
+    }
+
+    #[test]
+    fn untimed_triggers_match_the_stateless_check() {
+        let binding = Shortcuts::on_release(&[KeyCode::KeyE]);
+        let mut state = ShortcutState::default();
+        let mut keys = ButtonInput::<KeyCode>::default();
+        let time = Time::<()>::default();
+
+        keys.press(KeyCode::KeyE);
+        assert!(!state.update(&binding, &keys, &time));
+        keys.clear();
+        keys.release(KeyCode::KeyE);
+        assert!(state.update(&binding, &keys, &time));
+        assert_eq!(state.triggered(), binding.pressed(&keys));
+    }
+
+    /// Drives a `Tab` tap/hold pair through one press held for `hold_ms`
+    /// at 16ms frames, returning how many times each fired.
+    fn tap_hold_press(hold_ms: u64) -> (usize, usize) {
+        let threshold = Duration::from_millis(300);
+        let tap = Shortcuts::tap(&[KeyCode::Tab], threshold);
+        let hold = Shortcuts::hold(&[KeyCode::Tab], threshold);
+        let (mut tap_state, mut hold_state) = (ShortcutState::default(), ShortcutState::default());
+        let mut keys = ButtonInput::<KeyCode>::default();
+        let mut time = Time::<()>::default();
+        let (mut taps, mut holds) = (0, 0);
+        let mut step = |keys: &ButtonInput<KeyCode>, time: &Time| {
+            taps += usize::from(tap_state.update(&tap, keys, time));
+            holds += usize::from(hold_state.update(&hold, keys, time));
+        };
+
+        keys.press(KeyCode::Tab);
+        step(&keys, &time);
+        let mut elapsed = 0;
+        while elapsed + 16 <= hold_ms {
+            frame(&mut time, &mut keys, 16);
+            elapsed += 16;
+            step(&keys, &time);
+        }
+        frame(&mut time, &mut keys, 16);
+        keys.release(KeyCode::Tab);
+        step(&keys, &time);
+        // A few idle frames afterwards must not fire anything either.
+        for _ in 0..5 {
+            frame(&mut time, &mut keys, 16);
+            step(&keys, &time);
+        }
+        (taps, holds)
+    }
+
+    #[test]
+    fn a_quick_press_is_a_tap() {
+        assert_eq!(tap_hold_press(0), (1, 0));
+        assert_eq!(tap_hold_press(120), (1, 0));
+    }
+
+    #[test]
+    fn a_long_press_is_a_hold_and_fires_once() {
+        assert_eq!(tap_hold_press(600), (0, 1));
+        assert_eq!(tap_hold_press(2_000), (0, 1));
+    }
+
+    #[test]
+    fn every_press_is_exactly_one_of_tap_or_hold() {
+        for hold_ms in (0..=640).step_by(16) {
+            let (taps, holds) = tap_hold_press(hold_ms);
+            assert_eq!(taps + holds, 1, "held {hold_ms}ms");
+        }
+    }
+
+    #[test]
+    fn hold_fires_at_the_threshold_before_release() {
+        let hold = Shortcuts::hold(&[KeyCode::Tab], Duration::from_millis(300));
+        let mut state = ShortcutState::default();
+        let mut keys = ButtonInput::<KeyCode>::default();
+        let mut time = Time::<()>::default();
+
+        keys.press(KeyCode::Tab);
+        assert!(!state.update(&hold, &keys, &time));
+        frame(&mut time, &mut keys, 299);
+        assert!(!state.update(&hold, &keys, &time));
+        frame(&mut time, &mut keys, 1);
+        assert!(
+            state.update(&hold, &keys, &time),
+            "crossed while still held"
+        );
+        frame(&mut time, &mut keys, 100);
+        assert!(!state.update(&hold, &keys, &time));
+    }
+
+    /// Presses and releases `key` within one frame, then lets `gap_ms` pass.
+    fn tap_key(
+        key: KeyCode,
+        gap_ms: u64,
+        mut step: impl FnMut(&ButtonInput<KeyCode>, &Time),
+        keys: &mut ButtonInput<KeyCode>,
+        time: &mut Time,
+    ) {
+        keys.press(key);
+        step(keys, time);
+        frame(time, keys, gap_ms);
+        keys.release(key);
+        step(keys, time);
+        keys.clear();
+    }
+
+    #[test]
+    fn double_tap_fires_on_the_second_press_within_the_window() {
+        let dash = Shortcuts::multi_tap(&[KeyCode::KeyD], 2, Duration::from_millis(250));
+        let mut state = ShortcutState::default();
+        let mut keys = ButtonInput::<KeyCode>::default();
+        let mut time = Time::<()>::default();
+        let mut fired = Vec::new();
+
+        for _ in 0..2 {
+            tap_key(
+                KeyCode::KeyD,
+                100,
+                |k, t| fired.push(state.update(&dash, k, t)),
+                &mut keys,
+                &mut time,
+            );
+        }
+        assert_eq!(fired, [false, false, true, false]);
+
+        // Too slow: the second press starts over instead.
+        let mut state = ShortcutState::default();
+        keys.press(KeyCode::KeyD);
+        state.update(&dash, &keys, &time);
+        keys.release(KeyCode::KeyD);
+        frame(&mut time, &mut keys, 400);
+        state.update(&dash, &keys, &time);
+        keys.press(KeyCode::KeyD);
+        assert!(!state.update(&dash, &keys, &time));
+    }
+
+    #[test]
+    fn a_triple_tap_does_not_fire_a_double_tap_twice() {
+        let dash = Shortcuts::multi_tap(&[KeyCode::KeyD], 2, Duration::from_millis(250));
+        let mut state = ShortcutState::default();
+        let mut keys = ButtonInput::<KeyCode>::default();
+        let mut time = Time::<()>::default();
+        let mut count = 0;
+
+        for _ in 0..3 {
+            tap_key(
+                KeyCode::KeyD,
+                50,
+                |k, t| count += usize::from(state.update(&dash, k, t)),
+                &mut keys,
+                &mut time,
+            );
+        }
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn taps_on_different_alternatives_do_not_combine() {
+        let dash = Shortcuts::multi_tap(
+            &[KeyCode::KeyD, KeyCode::ArrowRight],
+            2,
+            Duration::from_millis(250),
+        );
+        let mut state = ShortcutState::default();
+        let mut keys = ButtonInput::<KeyCode>::default();
+        let mut time = Time::<()>::default();
+        let mut count = 0;
+
+        for key in [KeyCode::KeyD, KeyCode::ArrowRight] {
+            tap_key(
+                key,
+                50,
+                |k, t| count += usize::from(state.update(&dash, k, t)),
+                &mut keys,
+                &mut time,
+            );
+        }
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn unambiguous_single_tap_waits_out_the_window() {
+        let window = Duration::from_millis(250);
+        let deselect = Shortcuts::multi_tap(&[KeyCode::Escape], 1, window).unambiguous();
+        let clear = Shortcuts::multi_tap(&[KeyCode::Escape], 2, window);
+
+        let run = |presses: usize| {
+            let (mut single, mut double) = (ShortcutState::default(), ShortcutState::default());
+            let mut keys = ButtonInput::<KeyCode>::default();
+            let mut time = Time::<()>::default();
+            let (mut singles, mut doubles) = (Vec::new(), 0);
+            for _ in 0..presses {
+                tap_key(
+                    KeyCode::Escape,
+                    50,
+                    |k, t| {
+                        if single.update(&deselect, k, t) {
+                            singles.push(t.elapsed().as_millis());
+                        }
+                        doubles += usize::from(double.update(&clear, k, t));
+                    },
+                    &mut keys,
+                    &mut time,
+                );
+            }
+            for _ in 0..30 {
+                frame(&mut time, &mut keys, 16);
+                if single.update(&deselect, &keys, &time) {
+                    singles.push(time.elapsed().as_millis());
+                }
+                doubles += usize::from(double.update(&clear, &keys, &time));
+            }
+            (singles, doubles)
+        };
+
+        let (singles, doubles) = run(1);
+        assert_eq!(doubles, 0);
+        assert_eq!(singles.len(), 1);
+        assert!(singles[0] > 250, "only after the window ran out");
+
+        assert_eq!(run(2), (vec![], 1), "a double tap never fires the single");
+    }
+
+    #[test]
+    fn trigger_is_optional_in_config() {
+        let legacy: Shortcuts =
+            serde_json::from_str(r#"{ "repeats": true, "shortcuts": [{ "key": "KeyA" }] }"#)
+                .unwrap();
+        assert_eq!(legacy.trigger, Trigger::Press);
+
+        let charge: Shortcuts = serde_json::from_str(
+            r#"{ "trigger": { "ReleaseAfterHold": { "hold_ms": 800 } },
+                 "shortcuts": [{ "key": "Space" }] }"#,
+        )
+        .unwrap();
+        assert_eq!(charge.trigger, Trigger::ReleaseAfterHold { hold_ms: 800 });
+
+        let dash: Shortcuts = serde_json::from_str(
+            r#"{ "trigger": { "MultiTap": { "taps": 2, "window_ms": 250 } },
+                 "shortcuts": [{ "key": "KeyD" }] }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            dash.trigger,
+            Trigger::MultiTap {
+                taps: 2,
+                window_ms: 250,
+                unambiguous: false
+            }
+        );
+
+        // Bindings saved with the default trigger look as they did before
+        // triggers existed.
+        let saved = serde_json::to_value(&legacy).unwrap();
+        assert_eq!(saved.get("trigger"), None);
+        let saved = serde_json::to_value(&charge).unwrap();
+        assert!(saved.get("trigger").is_some());
+
+        let never = serde_json::from_str::<Shortcuts>(
+            r#"{ "trigger": { "MultiTap": { "taps": 0, "window_ms": 250 } },
+                 "shortcuts": [{ "key": "KeyD" }] }"#,
+        );
+        assert!(never.unwrap_err().to_string().contains("at least one tap"));
+    }
+}
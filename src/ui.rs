@@ -0,0 +1,275 @@
+//! Keyboard accelerators attached to UI nodes.
+
+use crate::Shortcuts;
+use bevy::ecs::entity_disabling::Disabled;
+use bevy::ecs::query::QueryData;
+use bevy::input::InputSystems;
+use bevy::input::keyboard::KeyCode;
+use bevy::prelude::*;
+
+/// A keyboard accelerator for a UI node, such as `Alt + F` on a File button.
+///
+/// With [`UiShortcutPlugin`], pressing the binding triggers
+/// [`ShortcutActivated`] on the node, as long as it is active: neither it nor
+/// any ancestor is [`Disabled`] or marked [`ShortcutsInactive`]. With the
+/// `ui` feature, a node hidden by its `InheritedVisibility` or under a
+/// `Display::None` ancestor is inactive too, so hiding a panel stops the
+/// accelerators of every button in it; without it, mark the panel inactive
+/// when hiding it.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_keyboard_shortcuts::{ShortcutActivated, Shortcuts, UiShortcut, UiShortcutPlugin};
+///
+/// fn spawn_menu_bar(mut commands: Commands) {
+///     let file = UiShortcut::new(Shortcuts::single_press(&[KeyCode::KeyF]).with_alt());
+///     let label = format!("File ({})", file.label());
+///     commands
+///         .spawn((Name::new(label), file))
+///         .observe(|_: On<ShortcutActivated>| {
+///             // Open the File menu, as a click would.
+///         });
+/// }
+///
+/// App::new()
+///     .add_plugins(UiShortcutPlugin)
+///     .add_systems(Startup, spawn_menu_bar);
+/// ```
+#[derive(Component, Debug, Clone)]
+pub struct UiShortcut {
+    /// The binding that activates the node
+    pub binding: Shortcuts,
+}
+
+impl UiShortcut {
+    /// Attaches `binding` to a node.
+    pub fn new(binding: Shortcuts) -> Self {
+        Self { binding }
+    }
+
+    /// The binding as a button label shows it: its first alternative, such as
+    /// `"Alt + F"`, or an empty string when unbound.
+    pub fn label(&self) -> String {
+        self.binding
+            .iter()
+            .next()
+            .map(ToString::to_string)
+            .unwrap_or_default()
+    }
+}
+
+/// Turns off the [`UiShortcut`]s of a node and everything below it, such as
+/// a hidden panel or a closed menu.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct ShortcutsInactive;
+
+/// Triggered on a node when its [`UiShortcut`] is pressed.
+///
+/// Like a pointer click, it bubbles up the hierarchy: observers on a menu
+/// bar hear the activation of each of its buttons, with the menu bar as
+/// [`entity`](Self::entity) and the button as `original_event_target`.
+#[derive(EntityEvent, Debug, Clone)]
+#[entity_event(propagate, auto_propagate)]
+pub struct ShortcutActivated {
+    /// The node whose shortcut was pressed, or an ancestor it bubbled to
+    pub entity: Entity,
+}
+
+/// Triggers [`ShortcutActivated`] on active [`UiShortcut`] nodes, after
+/// Bevy's input systems each frame.
+pub struct UiShortcutPlugin;
+
+impl Plugin for UiShortcutPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreUpdate, activate_ui_shortcuts.after(InputSystems));
+    }
+}
+
+/// What a node says about the shortcuts below it.
+#[derive(QueryData)]
+struct NodeState {
+    parent: Option<&'static ChildOf>,
+    inactive: Has<ShortcutsInactive>,
+    disabled: Has<Disabled>,
+    #[cfg(feature = "ui")]
+    visibility: Option<&'static InheritedVisibility>,
+    #[cfg(feature = "ui")]
+    node: Option<&'static Node>,
+}
+
+impl NodeStateItem<'_, '_> {
+    /// Whether the node turns off its own shortcuts and those below it.
+    fn turns_off(&self) -> bool {
+        #[cfg(feature = "ui")]
+        if self
+            .node
+            .is_some_and(|node| node.display == bevy::ui::Display::None)
+        {
+            return true;
+        }
+        self.inactive || self.disabled
+    }
+
+    /// Whether the node is drawn. Only asked of the node with the shortcut:
+    /// inherited visibility already accounts for its ancestors.
+    fn shown(&self) -> bool {
+        #[cfg(feature = "ui")]
+        if self.visibility.is_some_and(|visibility| !visibility.get()) {
+            return false;
+        }
+        true
+    }
+}
+
+/// Each node's state, disabled ones included.
+type Nodes<'w, 's> = Query<'w, 's, NodeState, Allow<Disabled>>;
+
+fn activate_ui_shortcuts(
+    keys: Res<ButtonInput<KeyCode>>,
+    shortcuts: Query<(Entity, &UiShortcut)>,
+    nodes: Nodes,
+    mut commands: Commands,
+) {
+    for (entity, shortcut) in &shortcuts {
+        if shortcut.binding.pressed(&keys) && is_active(entity, &nodes) {
+            commands.trigger(ShortcutActivated { entity });
+        }
+    }
+}
+
+/// Whether `entity` is shown and neither it nor any ancestor turns its
+/// shortcuts off.
+fn is_active(entity: Entity, nodes: &Nodes) -> bool {
+    if nodes.get(entity).is_ok_and(|state| !state.shown()) {
+        return false;
+    }
+    let mut next = Some(entity);
+    while let Some(node) = next {
+        let Ok(state) = nodes.get(node) else {
+            return true;
+        };
+        if state.turns_off() {
+            return false;
+        }
+        next = state.parent.map(ChildOf::parent);
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Activations(Vec<Entity>);
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins(UiShortcutPlugin)
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<Activations>();
+        app
+    }
+
+    fn press(app: &mut App, chord: &[KeyCode]) {
+        let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keys.release_all();
+        keys.clear();
+        for &key in chord {
+            keys.press(key);
+        }
+        app.update();
+    }
+
+    #[test]
+    fn activation_bubbles_from_the_node_to_its_ancestors() {
+        let mut app = app();
+        let menu_bar = app
+            .world_mut()
+            .spawn_empty()
+            .observe(
+                |activated: On<ShortcutActivated>, mut seen: ResMut<Activations>| {
+                    seen.0.push(activated.original_event_target());
+                },
+            )
+            .id();
+        let file = UiShortcut::new(Shortcuts::single_press(&[KeyCode::KeyF]).with_alt());
+        assert_eq!(file.label(), "Alt + F");
+        let file = app.world_mut().spawn((file, ChildOf(menu_bar))).id();
+
+        press(&mut app, &[KeyCode::AltLeft, KeyCode::KeyF]);
+        assert_eq!(app.world().resource::<Activations>().0, [file]);
+    }
+
+    #[test]
+    fn inactive_or_disabled_subtrees_do_not_fire() {
+        let mut app = app();
+        let panel = app.world_mut().spawn(ShortcutsInactive).id();
+        app.world_mut()
+            .spawn((
+                UiShortcut::new(Shortcuts::single_press(&[KeyCode::KeyS])),
+                ChildOf(panel),
+            ))
+            .observe(|_: On<ShortcutActivated>, mut seen: ResMut<Activations>| {
+                seen.0.push(Entity::PLACEHOLDER);
+            });
+
+        press(&mut app, &[KeyCode::KeyS]);
+        assert!(app.world().resource::<Activations>().0.is_empty());
+
+        app.world_mut()
+            .entity_mut(panel)
+            .remove::<ShortcutsInactive>()
+            .insert(Disabled);
+        press(&mut app, &[KeyCode::KeyS]);
+        assert!(app.world().resource::<Activations>().0.is_empty());
+
+        app.world_mut().entity_mut(panel).remove::<Disabled>();
+        press(&mut app, &[KeyCode::KeyS]);
+        assert_eq!(app.world().resource::<Activations>().0.len(), 1);
+    }
+
+    #[cfg(feature = "ui")]
+    #[test]
+    fn hidden_subtrees_do_not_fire() {
+        let mut app = app();
+        let panel = app
+            .world_mut()
+            .spawn((Node::default(), Visibility::Hidden))
+            .id();
+        // The visibility Bevy's propagation gives a child of a hidden node.
+        let button = app
+            .world_mut()
+            .spawn((
+                UiShortcut::new(Shortcuts::single_press(&[KeyCode::KeyS])),
+                Node::default(),
+                InheritedVisibility::HIDDEN,
+                ChildOf(panel),
+            ))
+            .observe(|_: On<ShortcutActivated>, mut seen: ResMut<Activations>| {
+                seen.0.push(Entity::PLACEHOLDER);
+            })
+            .id();
+
+        press(&mut app, &[KeyCode::KeyS]);
+        assert!(app.world().resource::<Activations>().0.is_empty());
+
+        app.world_mut()
+            .entity_mut(panel)
+            .insert(Visibility::Inherited);
+        app.world_mut()
+            .entity_mut(button)
+            .insert(InheritedVisibility::VISIBLE);
+        press(&mut app, &[KeyCode::KeyS]);
+        assert_eq!(app.world().resource::<Activations>().0.len(), 1);
+
+        app.world_mut().entity_mut(panel).insert(Node {
+            display: bevy::ui::Display::None,
+            ..default()
+        });
+        press(&mut app, &[KeyCode::KeyS]);
+        assert_eq!(app.world().resource::<Activations>().0.len(), 1);
+    }
+}
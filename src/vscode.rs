@@ -0,0 +1,491 @@
+//! Importing VS Code `keybindings.json` files.
+
+use crate::{
+    ActionOverride, Keymap, KeymapOverrides, ModifierType, Modifiers, Shortcut, Shortcuts,
+};
+use bevy::input::keyboard::KeyCode;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// The result of [`import_vscode_keybindings`]: the bindings that could be
+/// represented, and a report on the entries that could not.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct VsCodeImport {
+    /// Changes to the editor's default keymap, keyed by VS Code command name
+    pub overrides: KeymapOverrides,
+    /// Entries that were skipped or imported only in part, in file order
+    pub issues: Vec<VsCodeIssue>,
+}
+
+/// One `keybindings.json` entry that could not be imported as written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VsCodeIssue {
+    /// Position of the entry in the file's array, from 0
+    pub index: usize,
+    /// The entry's `key`
+    pub key: String,
+    /// The entry's `command`, including any leading `-`
+    pub command: String,
+    /// What could not be represented
+    pub problem: VsCodeProblem,
+}
+
+/// Why a `keybindings.json` entry could not be imported as written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VsCodeProblem {
+    /// A sequence such as `ctrl+k ctrl+s`; shortcuts are single chords.
+    /// The entry is skipped.
+    ChordSequence,
+    /// A key name with no [`KeyCode`]. The entry is skipped.
+    UnknownKey(String),
+    /// The entry has no `key`. The entry is skipped.
+    MissingKey,
+    /// The command takes `args`, so it is not the plain command. The entry
+    /// is skipped.
+    CommandArgs,
+    /// The entry only applies in a `when` context. The binding is imported
+    /// without its condition.
+    WhenClauseDropped(String),
+}
+
+impl VsCodeProblem {
+    /// Returns `true` if the entry was left out of the import entirely.
+    pub fn skipped(&self) -> bool {
+        !matches!(self, VsCodeProblem::WhenClauseDropped(_))
+    }
+}
+
+impl fmt::Display for VsCodeProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VsCodeProblem::ChordSequence => f.write_str("multi-chord sequences are not supported"),
+            VsCodeProblem::UnknownKey(key) => write!(f, "unknown key {key:?}"),
+            VsCodeProblem::MissingKey => f.write_str("no key"),
+            VsCodeProblem::CommandArgs => f.write_str("commands with args are not supported"),
+            VsCodeProblem::WhenClauseDropped(when) => {
+                write!(f, "imported without its `when` clause {when:?}")
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Entry {
+    #[serde(default)]
+    key: Option<String>,
+    command: String,
+    #[serde(default)]
+    when: Option<String>,
+    #[serde(default)]
+    args: Option<serde_json::Value>,
+}
+
+/// One command's accumulated changes, turned into an [`ActionOverride`]
+/// once the whole file is read.
+#[derive(Default)]
+struct Changes {
+    unbind_defaults: bool,
+    add: Vec<Shortcut>,
+    remove: Vec<Shortcut>,
+}
+
+/// Reads a VS Code `keybindings.json` into overrides of `defaults`, for
+/// tools that mimic VS Code and use its command names as action names.
+///
+/// The file is VS Code's own format — comments and trailing commas allowed —
+/// and, like it, describes changes to the default keymap:
+///
+/// - `{ "key": "ctrl+s", "command": "save" }` adds `Ctrl + S` to `save`
+/// - `{ "key": "ctrl+s", "command": "-save" }` removes it from `save`: the
+///   default alternative on that key with exactly those modifiers required
+/// - `{ "command": "-save" }` removes all of `save`'s default bindings
+///
+/// Keys are read as physical keys on a US layout (`ctrl+[` is
+/// `BracketLeft`), and VS Code's scan-code form (`[KeyA]`) is accepted too.
+/// As in VS Code, modifiers an entry does not mention must not be held:
+/// `ctrl+s` does not fire on `Ctrl + Shift + S`.
+///
+/// Fails only if the file is not a JSON array of entries; entries that
+/// cannot be represented are listed in [`VsCodeImport::issues`].
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy::prelude::KeyCode;
+/// use bevy_keyboard_shortcuts::{Keymap, Shortcuts, import_vscode_keybindings};
+///
+/// let defaults = Keymap::from_iter([(
+///     "workbench.action.files.save",
+///     Shortcuts::single_press(&[KeyCode::KeyS]).with_ctrl(),
+/// )]);
+///
+/// let import = import_vscode_keybindings(
+///     &defaults,
+///     r#"[
+///         // Save on F2 instead
+///         { "key": "f2", "command": "workbench.action.files.save" },
+///         { "key": "ctrl+s", "command": "-workbench.action.files.save" },
+///         { "key": "ctrl+k ctrl+s", "command": "workbench.action.openGlobalKeybindings" },
+///     ]"#,
+/// )
+/// .unwrap();
+///
+/// let keymap = defaults.with_overrides(&import.overrides);
+/// assert_eq!(keymap.get("workbench.action.files.save").unwrap().to_string(), "F2");
+/// assert_eq!(import.issues.len(), 1, "the two-chord sequence");
+/// ```
+pub fn import_vscode_keybindings(
+    defaults: &Keymap,
+    json: &str,
+) -> Result<VsCodeImport, serde_json::Error> {
+    let entries: Vec<Entry> = serde_json::from_str(&strip_jsonc(json))?;
+
+    let mut changes: BTreeMap<String, Changes> = BTreeMap::new();
+    let mut issues = Vec::new();
+    for (index, entry) in entries.into_iter().enumerate() {
+        let mut report = |problem| {
+            issues.push(VsCodeIssue {
+                index,
+                key: entry.key.clone().unwrap_or_default(),
+                command: entry.command.clone(),
+                problem,
+            })
+        };
+        let (name, removal) = match entry.command.strip_prefix('-') {
+            Some(name) => (name, true),
+            None => (entry.command.as_str(), false),
+        };
+
+        if entry.args.is_some() {
+            report(VsCodeProblem::CommandArgs);
+            continue;
+        }
+        let shortcut = match entry.key.as_deref().map(parse_chord) {
+            Some(Ok(shortcut)) => Some(shortcut),
+            Some(Err(problem)) => {
+                report(problem);
+                continue;
+            }
+            None if removal => None,
+            None => {
+                report(VsCodeProblem::MissingKey);
+                continue;
+            }
+        };
+        if let Some(when) = &entry.when {
+            report(VsCodeProblem::WhenClauseDropped(when.clone()));
+        }
+
+        let command = changes.entry(name.to_string()).or_default();
+        match (shortcut, removal) {
+            (Some(shortcut), false) => {
+                command.remove.retain(|s| *s != shortcut);
+                if !command.add.contains(&shortcut) {
+                    command.add.push(shortcut);
+                }
+            }
+            (Some(shortcut), true) => {
+                command.add.retain(|s| *s != shortcut);
+                let default = defaults
+                    .get(name)
+                    .and_then(|d| d.iter().find(|d| same_chord(d, &shortcut)));
+                let shortcut = default.cloned().unwrap_or(shortcut);
+                if !command.remove.contains(&shortcut) {
+                    command.remove.push(shortcut);
+                }
+            }
+            (None, _) => {
+                command.unbind_defaults = true;
+                command.add.clear();
+                command.remove.clear();
+            }
+        }
+    }
+
+    let mut overrides = KeymapOverrides::new();
+    for (command, changes) in changes {
+        let change = match changes {
+            Changes {
+                unbind_defaults: true,
+                add,
+                ..
+            } if add.is_empty() => ActionOverride::Unbind,
+            Changes {
+                unbind_defaults: true,
+                add,
+                ..
+            } => ActionOverride::Replace(Shortcuts {
+                shortcuts: add,
+                ..Shortcuts::default()
+            }),
+            Changes { add, remove, .. } => ActionOverride::Edit { add, remove },
+        };
+        overrides.insert(command, change);
+    }
+    Ok(VsCodeImport { overrides, issues })
+}
+
+/// Whether `a` and `b` are pressed with the same key and require the same
+/// modifiers, however they treat the rest.
+fn same_chord(a: &Shortcut, b: &Shortcut) -> bool {
+    a.input == b.input && a.modifiers.required_names() == b.modifiers.required_names()
+}
+
+/// Parses one chord such as `ctrl+shift+[`.
+fn parse_chord(chord: &str) -> Result<Shortcut, VsCodeProblem> {
+    let chord = chord.trim().to_lowercase();
+    if chord.contains(char::is_whitespace) {
+        return Err(VsCodeProblem::ChordSequence);
+    }
+
+    let mut modifiers = Modifiers::new(
+        Some(ModifierType::RequireNotPressed),
+        Some(ModifierType::RequireNotPressed),
+        Some(ModifierType::RequireNotPressed),
+        Some(ModifierType::RequireNotPressed),
+    );
+    // The key is last; splitting from the right keeps `ctrl+=` and a bare
+    // `+` in one piece.
+    let (held, key) = match chord.rsplit_once('+') {
+        Some((held, "")) => (held.strip_suffix('+').unwrap_or(""), "+"),
+        Some((held, key)) => (held, key),
+        None => ("", chord.as_str()),
+    };
+    for modifier in held.split('+').filter(|m| !m.is_empty()) {
+        let slot = match modifier {
+            "ctrl" => &mut modifiers.control,
+            "alt" => &mut modifiers.alt,
+            "shift" => &mut modifiers.shift,
+            "meta" | "cmd" | "win" => &mut modifiers.super_key,
+            other => return Err(VsCodeProblem::UnknownKey(other.to_string())),
+        };
+        *slot = Some(ModifierType::RequirePressed);
+    }
+    let key = vscode_key(key).ok_or_else(|| VsCodeProblem::UnknownKey(key.to_string()))?;
+    Ok(Shortcut {
+        input: key.into(),
+        modifiers,
+    })
+}
+
+/// The [`KeyCode`] of a VS Code key name, lowercased.
+fn vscode_key(name: &str) -> Option<KeyCode> {
+    // Scan-code form: `[KeyA]` is already a `KeyCode` name, but arrives here
+    // lowercased, so match it case-insensitively against the variant names.
+    if let Some(code) = name.strip_prefix('[').and_then(|n| n.strip_suffix(']')) {
+        return crate::schema::key_code_names()
+            .find(|variant| variant.eq_ignore_ascii_case(code))
+            .and_then(|variant| serde_json::from_value(variant.into()).ok());
+    }
+
+    if let &[c] = name.as_bytes()
+        && c.is_ascii_alphanumeric()
+    {
+        let variant = match c {
+            b'a'..=b'z' => format!("Key{}", c.to_ascii_uppercase() as char),
+            _ => format!("Digit{}", c as char),
+        };
+        return serde_json::from_value(variant.into()).ok();
+    }
+    if let Some(n) = name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+        return serde_json::from_value(format!("F{n}").into()).ok();
+    }
+    if let Some(n) = name
+        .strip_prefix("numpad")
+        .and_then(|n| n.parse::<u8>().ok())
+    {
+        return serde_json::from_value(format!("Numpad{n}").into()).ok();
+    }
+
+    Some(match name {
+        "`" => KeyCode::Backquote,
+        "-" => KeyCode::Minus,
+        "=" => KeyCode::Equal,
+        "[" => KeyCode::BracketLeft,
+        "]" => KeyCode::BracketRight,
+        "\\" => KeyCode::Backslash,
+        ";" => KeyCode::Semicolon,
+        "'" => KeyCode::Quote,
+        "," => KeyCode::Comma,
+        "." => KeyCode::Period,
+        "/" => KeyCode::Slash,
+        "oem_102" => KeyCode::IntlBackslash,
+        "left" => KeyCode::ArrowLeft,
+        "up" => KeyCode::ArrowUp,
+        "right" => KeyCode::ArrowRight,
+        "down" => KeyCode::ArrowDown,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "insert" => KeyCode::Insert,
+        "delete" => KeyCode::Delete,
+        "backspace" => KeyCode::Backspace,
+        "tab" => KeyCode::Tab,
+        "enter" => KeyCode::Enter,
+        "escape" => KeyCode::Escape,
+        "space" => KeyCode::Space,
+        "capslock" => KeyCode::CapsLock,
+        "numlock" => KeyCode::NumLock,
+        "scrolllock" => KeyCode::ScrollLock,
+        "pausebreak" => KeyCode::Pause,
+        "printscreen" => KeyCode::PrintScreen,
+        "contextmenu" => KeyCode::ContextMenu,
+        "numpad_multiply" => KeyCode::NumpadMultiply,
+        "numpad_add" => KeyCode::NumpadAdd,
+        "numpad_separator" => KeyCode::NumpadComma,
+        "numpad_subtract" => KeyCode::NumpadSubtract,
+        "numpad_decimal" => KeyCode::NumpadDecimal,
+        "numpad_divide" => KeyCode::NumpadDivide,
+        _ => return None,
+    })
+}
+
+/// Blanks out `//` and `/* */` comments and trailing commas, which VS Code
+/// allows in its settings files, keeping every line break so that JSON
+/// errors still point at the right line.
+fn strip_jsonc(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => while chars.next_if(|&c| c != '\n').is_some() {},
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                    }
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+
+    // Trailing commas: a comma followed only by whitespace before `]` or `}`.
+    let mut cleaned = String::with_capacity(out.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in out.char_indices() {
+        if in_string {
+            in_string = escaped || c != '"';
+            escaped = !escaped && c == '\\';
+        } else if c == '"' {
+            in_string = true;
+        } else if c == ',' && matches!(out[i + 1..].trim_start().chars().next(), Some(']' | '}')) {
+            cleaned.push(' ');
+            continue;
+        }
+        cleaned.push(c);
+    }
+    cleaned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Keymap;
+
+    fn chord(key: &str) -> Shortcut {
+        parse_chord(key).unwrap()
+    }
+
+    #[test]
+    fn chords_map_to_physical_keys_with_exact_modifiers() {
+        assert_eq!(chord("ctrl+shift+[").to_string(), "Ctrl + Shift + [");
+        assert_eq!(chord("cmd+=").input, KeyCode::Equal.into());
+        assert_eq!(chord("ctrl+numpad_add").input, KeyCode::NumpadAdd.into());
+        assert_eq!(chord("alt+[KeyQ]").input, KeyCode::KeyQ.into());
+        assert_eq!(chord("F12").input, KeyCode::F12.into());
+        assert_eq!(chord("shift+7").input, KeyCode::Digit7.into());
+
+        let ctrl_s = chord("ctrl+s");
+        assert_eq!(
+            ctrl_s.modifiers.shift,
+            Some(ModifierType::RequireNotPressed)
+        );
+        assert_eq!(ctrl_s.modifiers.control, Some(ModifierType::RequirePressed));
+
+        assert_eq!(
+            parse_chord("ctrl+k ctrl+s"),
+            Err(VsCodeProblem::ChordSequence)
+        );
+        assert_eq!(
+            parse_chord("ctrl+abnt_c1"),
+            Err(VsCodeProblem::UnknownKey("abnt_c1".into()))
+        );
+    }
+
+    #[test]
+    fn removals_and_additions_apply_over_the_defaults() {
+        let defaults = Keymap::from_iter([
+            (
+                "editor.action.commentLine",
+                Shortcuts::single_press(&[KeyCode::Slash]).with_ctrl(),
+            ),
+            (
+                "workbench.action.quickOpen",
+                Shortcuts::single_press(&[KeyCode::KeyP]).with_ctrl(),
+            ),
+            (
+                "workbench.action.files.save",
+                Shortcuts::single_press(&[KeyCode::KeyS]).with_ctrl(),
+            ),
+        ]);
+        let import = import_vscode_keybindings(
+            &defaults,
+            r#"
+            /* Exported from VS Code */
+            [
+                { "key": "ctrl+/", "command": "-editor.action.commentLine", "when": "editorTextFocus" },
+                { "key": "ctrl+;", "command": "editor.action.commentLine" },
+                { "command": "-workbench.action.quickOpen" },
+                { "key": "ctrl+e", "command": "workbench.action.quickOpen" }, // like Sublime
+                { "key": "ctrl+t", "command": "workbench.action.terminal.sendSequence", "args": { "text": "ls\n" } },
+            ]
+            "#,
+        )
+        .unwrap();
+        let keymap = defaults.with_overrides(&import.overrides);
+
+        let binding = |action: &str| keymap.get(action).unwrap().to_string();
+        assert_eq!(binding("editor.action.commentLine"), "Ctrl + ;");
+        assert_eq!(binding("workbench.action.quickOpen"), "Ctrl + E");
+        assert_eq!(binding("workbench.action.files.save"), "Ctrl + S");
+
+        let problems: Vec<_> = import
+            .issues
+            .iter()
+            .map(|i| (i.index, i.problem.skipped()))
+            .collect();
+        assert_eq!(problems, [(0, false), (4, true)]);
+    }
+
+    #[test]
+    fn comments_and_trailing_commas_do_not_leak_into_strings() {
+        assert_eq!(
+            strip_jsonc("[ \"a//b\", /* c */ \"d,]\", // e\n ]"),
+            "[ \"a//b\",  \"d,]\"  \n ]"
+        );
+    }
+}
@@ -0,0 +1,270 @@
+//! Mouse wheel notches as bindable inputs.
+
+use crate::input::{ButtonSource, InputButton};
+use crate::{KeyState, Shortcuts, Trigger};
+use bevy::input::InputSystems;
+use bevy::input::mouse::{AccumulatedMouseScroll, MouseScrollUnit};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A direction the mouse wheel turns, or a touchpad scrolls.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum WheelDirection {
+    /// Away from the user, revealing content above
+    Up,
+    /// Towards the user, revealing content below
+    Down,
+    /// Revealing content to the left
+    Left,
+    /// Revealing content to the right
+    Right,
+}
+
+/// The mouse wheel, counted in whole notches.
+///
+/// Wheel bindings fire once per notch, so scrolling is accumulated across
+/// frames: half a notch this frame and half the next make one notch then.
+/// Touchpads report pixels rather than lines; `pixels_per_notch` of them
+/// count as one notch.
+///
+/// [`MouseWheelPlugin`] updates it each frame from Bevy's
+/// [`AccumulatedMouseScroll`].
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+pub struct MouseWheelNotches {
+    /// How many pixels of touchpad scrolling make one notch
+    pub pixels_per_notch: f32,
+    /// Scrolling that has not yet added up to a whole notch, in notches
+    remainder: Vec2,
+    /// Whole notches completed this frame, signed as Bevy reports scrolling
+    notches: IVec2,
+    /// Scrolling this frame, in notches
+    delta: Vec2,
+}
+
+impl Default for MouseWheelNotches {
+    fn default() -> Self {
+        Self {
+            pixels_per_notch: MouseScrollUnit::SCROLL_UNIT_CONVERSION_FACTOR,
+            remainder: Vec2::ZERO,
+            notches: IVec2::ZERO,
+            delta: Vec2::ZERO,
+        }
+    }
+}
+
+impl MouseWheelNotches {
+    /// Adds one frame of scrolling.
+    pub fn update(&mut self, scroll: &AccumulatedMouseScroll) {
+        self.delta = match scroll.unit {
+            MouseScrollUnit::Line => scroll.delta,
+            MouseScrollUnit::Pixel => scroll.delta / self.pixels_per_notch,
+        };
+        // Turning back drops the part-notch left over from the other way, so
+        // the first notch of the new direction is not spent cancelling it.
+        if self.remainder.x * self.delta.x < 0.0 {
+            self.remainder.x = 0.0;
+        }
+        if self.remainder.y * self.delta.y < 0.0 {
+            self.remainder.y = 0.0;
+        }
+        self.remainder += self.delta;
+        let whole = self.remainder.trunc();
+        self.notches = whole.as_ivec2();
+        self.remainder -= whole;
+    }
+
+    /// Whole notches completed in `direction` this frame.
+    pub fn notches(&self, direction: WheelDirection) -> u32 {
+        let signed = match direction {
+            WheelDirection::Up => self.notches.y,
+            WheelDirection::Down => -self.notches.y,
+            WheelDirection::Left => self.notches.x,
+            WheelDirection::Right => -self.notches.x,
+        };
+        signed.max(0) as u32
+    }
+
+    /// How far the wheel turned in `direction` this frame, in notches,
+    /// including fractions of one. Zero if it turned the other way.
+    pub fn magnitude(&self, direction: WheelDirection) -> f32 {
+        let signed = match direction {
+            WheelDirection::Up => self.delta.y,
+            WheelDirection::Down => -self.delta.y,
+            WheelDirection::Left => self.delta.x,
+            WheelDirection::Right => -self.delta.x,
+        };
+        signed.max(0.0)
+    }
+
+    /// This frame's scrolling in notches, signed as Bevy reports it:
+    /// positive `y` is up, positive `x` is left.
+    pub fn delta(&self) -> Vec2 {
+        self.delta
+    }
+}
+
+impl Shortcuts {
+    /// Creates shortcuts that fire once per notch the wheel turns in one of
+    /// `directions` (no modifiers). Read them with [`Shortcuts::scrolled`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bevy_keyboard_shortcuts::{Shortcuts, WheelDirection};
+    ///
+    /// let zoom_in = Shortcuts::wheel(&[WheelDirection::Up]).with_ctrl();
+    /// assert_eq!(zoom_in.to_string(), "Ctrl + Wheel Up");
+    /// ```
+    pub fn wheel(directions: &[WheelDirection]) -> Self {
+        Self {
+            shortcuts: directions
+                .iter()
+                .map(|&direction| crate::Shortcut {
+                    input: InputButton::Wheel(direction),
+                    modifiers: Default::default(),
+                })
+                .collect(),
+            repeats: false,
+            trigger: Trigger::Press,
+        }
+    }
+
+    /// How many times the wheel alternatives fire this frame: one per notch
+    /// turned in a bound direction while that alternative's modifiers match.
+    ///
+    /// Key and button alternatives are not counted here; check them with
+    /// [`Shortcuts::pressed`] as usual.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use bevy_keyboard_shortcuts::{MouseWheelNotches, Shortcuts, WheelDirection};
+    ///
+    /// fn zoom(keyboard: Res<ButtonInput<KeyCode>>, wheel: Res<MouseWheelNotches>) {
+    ///     let zoom_in = Shortcuts::wheel(&[WheelDirection::Up]).with_ctrl();
+    ///     for _ in 0..zoom_in.scrolled(&keyboard, &wheel) {
+    ///         // Zoom in one step
+    ///     }
+    /// }
+    /// ```
+    pub fn scrolled(&self, keys: &impl KeyState, wheel: &MouseWheelNotches) -> u32 {
+        self.wheel_alternatives(keys)
+            .map(|direction| wheel.notches(direction))
+            .sum()
+    }
+
+    /// How far the wheel turned this frame in the bound directions, in
+    /// notches, while the modifiers match. Unlike [`Shortcuts::scrolled`] this
+    /// includes fractions of a notch, for smooth touchpad zooming.
+    pub fn scroll_magnitude(&self, keys: &impl KeyState, wheel: &MouseWheelNotches) -> f32 {
+        self.wheel_alternatives(keys)
+            .map(|direction| wheel.magnitude(direction))
+            .sum()
+    }
+
+    /// Directions of the wheel alternatives whose modifiers are held.
+    fn wheel_alternatives<'a>(
+        &'a self,
+        keys: &'a impl KeyState,
+    ) -> impl Iterator<Item = WheelDirection> + 'a {
+        self.shortcuts
+            .iter()
+            .filter_map(|shortcut| match shortcut.input {
+                InputButton::Wheel(direction) if keys.modifiers_pressed(&shortcut.modifiers) => {
+                    Some(direction)
+                }
+                _ => None,
+            })
+    }
+}
+
+/// Adds the [`MouseWheelNotches`] resource and updates it after Bevy's input
+/// systems each frame.
+pub struct MouseWheelPlugin;
+
+impl Plugin for MouseWheelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MouseWheelNotches>()
+            .init_resource::<AccumulatedMouseScroll>()
+            .add_systems(PreUpdate, count_wheel_notches.after(InputSystems));
+    }
+}
+
+fn count_wheel_notches(scroll: Res<AccumulatedMouseScroll>, mut wheel: ResMut<MouseWheelNotches>) {
+    wheel.update(&scroll);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ModifierType, Shortcut};
+
+    fn scroll(unit: MouseScrollUnit, x: f32, y: f32) -> AccumulatedMouseScroll {
+        AccumulatedMouseScroll {
+            unit,
+            delta: Vec2::new(x, y),
+        }
+    }
+
+    #[test]
+    fn pixel_scrolling_accumulates_into_notches() {
+        let mut wheel = MouseWheelNotches::default();
+
+        let fired: Vec<u32> = [40.0, 40.0, 40.0, 190.0]
+            .into_iter()
+            .map(|pixels| {
+                wheel.update(&scroll(MouseScrollUnit::Pixel, 0.0, -pixels));
+                wheel.notches(WheelDirection::Down)
+            })
+            .collect();
+        assert_eq!(fired, [0, 0, 1, 2]);
+        assert_eq!(wheel.notches(WheelDirection::Up), 0);
+        assert!((wheel.magnitude(WheelDirection::Down) - 1.9).abs() < 1e-5);
+
+        // A line up right after scrolling down is a notch up, whatever part
+        // of a notch down was left over.
+        wheel.update(&scroll(MouseScrollUnit::Line, 3.0, 1.0));
+        assert_eq!(wheel.notches(WheelDirection::Left), 3);
+        assert_eq!(wheel.notches(WheelDirection::Up), 1);
+    }
+
+    #[test]
+    fn wheel_bindings_fire_per_notch_with_their_modifiers() {
+        let zoom_in = Shortcuts::wheel(&[WheelDirection::Up]).with_ctrl();
+        let scroll_up = Shortcuts::wheel(&[WheelDirection::Up]).without_ctrl();
+        let mut keys = ButtonInput::<KeyCode>::default();
+        let mut wheel = MouseWheelNotches::default();
+
+        wheel.update(&scroll(MouseScrollUnit::Line, 0.0, 2.5));
+        assert_eq!(zoom_in.scrolled(&keys, &wheel), 0);
+        assert_eq!(scroll_up.scrolled(&keys, &wheel), 2);
+        assert_eq!(scroll_up.scroll_magnitude(&keys, &wheel), 2.5);
+
+        keys.press(KeyCode::ControlRight);
+        wheel.update(&scroll(MouseScrollUnit::Line, 0.0, 0.5));
+        assert_eq!(
+            zoom_in.scrolled(&keys, &wheel),
+            1,
+            "the half notch left over"
+        );
+        assert_eq!(scroll_up.scrolled(&keys, &wheel), 0);
+        assert!(!zoom_in.pressed(&keys), "wheels are counted, not pressed");
+    }
+
+    #[test]
+    fn wheel_bindings_read_and_write_a_wheel_field() {
+        let json = r#"{"wheel":"Down","modifiers":{"shift":"RequirePressed"}}"#;
+        let shortcut: Shortcut = serde_json::from_str(json).unwrap();
+
+        assert_eq!(shortcut.input, InputButton::Wheel(WheelDirection::Down));
+        assert_eq!(shortcut.modifiers.shift, Some(ModifierType::RequirePressed));
+        assert_eq!(shortcut.to_string(), "Shift + Wheel Down");
+        let written = serde_json::to_value(&shortcut).unwrap();
+        assert_eq!(written["wheel"], "Down");
+        assert_eq!(
+            serde_json::from_value::<Shortcut>(written).unwrap(),
+            shortcut
+        );
+    }
+}
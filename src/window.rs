@@ -0,0 +1,313 @@
+//! Keyboard state kept per window, for tools with more than one window.
+
+use crate::KeySet;
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::{ButtonState, InputSystems};
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy::window::{WindowClosed, WindowFocused};
+
+/// Which window's keyboard a binding or keymap listens to.
+///
+/// As a component beside [`ShortcutBindings`](crate::ShortcutBindings), it
+/// restricts that entity's actions: [`ActionStatePlugin`](crate::ActionStatePlugin)
+/// updates them from [`WindowKeys::scoped`] instead of the merged keyboard.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_keyboard_shortcuts::{ShortcutBindings, Shortcuts, WindowScope};
+///
+/// #[derive(Clone, PartialEq, Eq, Hash)]
+/// enum InspectorAction {
+///     DeleteComponent,
+/// }
+///
+/// fn spawn_inspector(mut commands: Commands) {
+///     let window = commands.spawn(Window::default()).id();
+///     commands.entity(window).insert((
+///         ShortcutBindings::from_iter([(
+///             InspectorAction::DeleteComponent,
+///             Shortcuts::single_press(&[KeyCode::Delete]),
+///         )]),
+///         // Delete typed into the viewport window does not reach it.
+///         WindowScope::Window(window),
+///     ));
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WindowScope {
+    /// Whichever window has keyboard focus (default)
+    #[default]
+    Focused,
+    /// One window only: its shortcuts never fire from keys typed elsewhere
+    Window(Entity),
+}
+
+/// The keyboard as each window saw it.
+///
+/// Bevy's [`ButtonInput<KeyCode>`] merges every window, so a shortcut fires
+/// whichever window the key was typed into. `WindowKeys` keeps a [`KeySet`]
+/// per window from the [`KeyboardInput`] messages instead; match a binding
+/// against [`WindowKeys::window`] to restrict it to one window, or against
+/// [`WindowKeys::focused`] to follow focus. A window that loses focus has
+/// its keys released there and then, since their releases go to whichever
+/// window gained it.
+///
+/// [`WindowKeysPlugin`] updates it each frame.
+///
+/// # Examples
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_keyboard_shortcuts::{Shortcuts, WindowKeys};
+///
+/// #[derive(Component)]
+/// struct Inspector;
+///
+/// fn delete_selected(windows: Res<WindowKeys>, inspector: Single<Entity, With<Inspector>>) {
+///     let delete = Shortcuts::single_press(&[KeyCode::Delete]);
+///     // Delete in the viewport window deletes entities instead.
+///     if delete.pressed(windows.window(*inspector)) {
+///         // Delete the selected component
+///     }
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Default)]
+pub struct WindowKeys {
+    windows: HashMap<Entity, KeySet>,
+    focused: Option<Entity>,
+    /// Returned for windows that have seen no keys
+    empty: KeySet,
+}
+
+impl WindowKeys {
+    /// Applies one key going down or up in its window.
+    pub fn handle_keyboard(&mut self, input: &KeyboardInput) {
+        let keys = self.windows.entry(input.window).or_default();
+        match input.state {
+            ButtonState::Pressed => keys.press(input.key_code),
+            ButtonState::Released => keys.release(input.key_code),
+        }
+    }
+
+    /// Applies a focus change. The window losing focus has its held keys
+    /// released.
+    pub fn handle_focus(&mut self, event: &WindowFocused) {
+        if event.focused {
+            self.focused = Some(event.window);
+            return;
+        }
+        if self.focused == Some(event.window) {
+            self.focused = None;
+        }
+        if let Some(keys) = self.windows.get_mut(&event.window) {
+            keys.release_all();
+        }
+    }
+
+    /// Forgets a closed window.
+    pub fn remove_window(&mut self, window: Entity) {
+        self.windows.remove(&window);
+        if self.focused == Some(window) {
+            self.focused = None;
+        }
+    }
+
+    /// Ends the frame in every window, as [`KeySet::clear`] does.
+    pub fn clear(&mut self) {
+        for keys in self.windows.values_mut() {
+            keys.clear();
+        }
+    }
+
+    /// The keys held in `window`.
+    pub fn window(&self, window: Entity) -> &KeySet {
+        self.windows.get(&window).unwrap_or(&self.empty)
+    }
+
+    /// The window with keyboard focus, if any has it.
+    pub fn focused_window(&self) -> Option<Entity> {
+        self.focused
+    }
+
+    /// The keys held in the window with keyboard focus; none while no
+    /// window has it.
+    pub fn focused(&self) -> &KeySet {
+        self.focused
+            .map_or(&self.empty, |window| self.window(window))
+    }
+
+    /// The keys a binding scoped to `scope` listens to.
+    pub fn scoped(&self, scope: WindowScope) -> &KeySet {
+        match scope {
+            WindowScope::Focused => self.focused(),
+            WindowScope::Window(window) => self.window(window),
+        }
+    }
+}
+
+/// Adds the [`WindowKeys`] resource and updates it after Bevy's input systems
+/// each frame.
+pub struct WindowKeysPlugin;
+
+impl Plugin for WindowKeysPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WindowKeys>()
+            .add_message::<KeyboardInput>()
+            .add_message::<WindowFocused>()
+            .add_message::<WindowClosed>()
+            .add_systems(PreUpdate, track_window_keys.after(InputSystems));
+    }
+}
+
+pub(crate) fn track_window_keys(
+    mut keyboard: MessageReader<KeyboardInput>,
+    mut focus: MessageReader<WindowFocused>,
+    mut closed: MessageReader<WindowClosed>,
+    mut windows: ResMut<WindowKeys>,
+) {
+    windows.clear();
+    // Keys before focus: a window gets no keys once it has lost focus, so a
+    // key pressed there this frame must be pressed before it is let go.
+    for input in keyboard.read() {
+        windows.handle_keyboard(input);
+    }
+    for event in focus.read() {
+        windows.handle_focus(event);
+    }
+    for event in closed.read() {
+        windows.remove_window(event.window);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ActionState, ActionStatePlugin, KeyState, ShortcutBindings, Shortcuts};
+    use bevy::input::keyboard::{Key, NativeKey};
+
+    fn key(window: Entity, key_code: KeyCode, state: ButtonState) -> KeyboardInput {
+        KeyboardInput {
+            key_code,
+            logical_key: Key::Unidentified(NativeKey::Unidentified),
+            state,
+            text: None,
+            repeat: false,
+            window,
+        }
+    }
+
+    fn focus(window: Entity, focused: bool) -> WindowFocused {
+        WindowFocused { window, focused }
+    }
+
+    #[test]
+    fn bindings_follow_the_window_keys_were_typed_into() {
+        let mut world = World::new();
+        let viewport = world.spawn_empty().id();
+        let inspector = world.spawn_empty().id();
+        let delete = Shortcuts::single_press(&[KeyCode::Delete]);
+        let mut windows = WindowKeys::default();
+
+        windows.handle_focus(&focus(inspector, true));
+        windows.handle_keyboard(&key(inspector, KeyCode::Delete, ButtonState::Pressed));
+        assert!(delete.pressed(windows.window(inspector)));
+        assert!(delete.pressed(windows.focused()));
+        assert!(!delete.pressed(windows.window(viewport)));
+        assert!(delete.pressed(windows.scoped(WindowScope::Focused)));
+        assert!(!delete.pressed(windows.scoped(WindowScope::Window(viewport))));
+
+        // Clicking into the viewport with Delete still down: its release
+        // goes to the viewport, so the inspector lets go when focus leaves.
+        windows.clear();
+        windows.handle_focus(&focus(inspector, false));
+        windows.handle_focus(&focus(viewport, true));
+        assert!(!windows.window(inspector).pressed(KeyCode::Delete));
+        assert!(windows.window(inspector).just_released(KeyCode::Delete));
+        assert_eq!(windows.focused_window(), Some(viewport));
+        assert!(!delete.pressed(windows.focused()));
+    }
+
+    #[test]
+    fn plugin_tracks_keys_per_window() {
+        let mut app = App::new();
+        app.add_plugins(WindowKeysPlugin);
+        let viewport = app.world_mut().spawn_empty().id();
+        let inspector = app.world_mut().spawn_empty().id();
+
+        app.world_mut().write_message(focus(viewport, true));
+        app.world_mut()
+            .write_message(key(viewport, KeyCode::KeyS, ButtonState::Pressed));
+        app.update();
+        let windows = app.world().resource::<WindowKeys>();
+        assert!(windows.focused().just_pressed(KeyCode::KeyS));
+        assert!(!windows.window(inspector).pressed(KeyCode::KeyS));
+
+        app.update();
+        let windows = app.world().resource::<WindowKeys>();
+        assert!(
+            windows.window(viewport).pressed(KeyCode::KeyS),
+            "still held"
+        );
+        assert!(!windows.window(viewport).just_pressed(KeyCode::KeyS));
+
+        app.world_mut()
+            .write_message(WindowClosed { window: viewport });
+        app.update();
+        let windows = app.world().resource::<WindowKeys>();
+        assert_eq!(windows.focused_window(), None);
+        assert!(!windows.window(viewport).pressed(KeyCode::KeyS));
+    }
+
+    #[test]
+    fn scoped_bindings_only_hear_their_window() {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        struct Delete;
+
+        let mut app = App::new();
+        app.add_plugins((
+            bevy::time::TimePlugin,
+            WindowKeysPlugin,
+            ActionStatePlugin::<Delete>::default(),
+        ))
+        .init_resource::<ButtonInput<KeyCode>>();
+        let viewport = app.world_mut().spawn_empty().id();
+        let inspector = app.world_mut().spawn_empty().id();
+        let bindings =
+            || ShortcutBindings::from_iter([(Delete, Shortcuts::single_press(&[KeyCode::Delete]))]);
+        let scoped = app
+            .world_mut()
+            .spawn((bindings(), WindowScope::Window(inspector)))
+            .id();
+        let focused = app
+            .world_mut()
+            .spawn((bindings(), WindowScope::Focused))
+            .id();
+        let fired = |app: &App, entity| {
+            app.world()
+                .get::<ActionState<Delete>>(entity)
+                .unwrap()
+                .pressed(&Delete)
+        };
+
+        app.world_mut().write_message(focus(viewport, true));
+        app.world_mut()
+            .write_message(key(viewport, KeyCode::Delete, ButtonState::Pressed));
+        app.update();
+        assert!(!fired(&app, scoped), "typed into the viewport");
+        assert!(fired(&app, focused));
+
+        app.world_mut()
+            .write_message(key(viewport, KeyCode::Delete, ButtonState::Released));
+        app.world_mut().write_message(focus(viewport, false));
+        app.world_mut().write_message(focus(inspector, true));
+        app.update();
+        app.world_mut()
+            .write_message(key(inspector, KeyCode::Delete, ButtonState::Pressed));
+        app.update();
+        assert!(fired(&app, scoped));
+        assert!(fired(&app, focused));
+    }
+}